@@ -1,19 +1,71 @@
-use std::ops::Index;
-
+use alloc::vec::{self, Vec};
+use core::ops::Index;
+
+/// Genes, optionally accompanied by strategy parameters - e.g. mutation
+/// step sizes for `SelfAdaptiveMutation`.
+///
+/// Strategy parameters are evolved along the genes, but aren't genes
+/// themselves: `len()`, `iter()`, indexing and `into_iter()` see only the
+/// genes, so decoding a chromosome (e.g. into network's weights) doesn't
+/// have to care about them.
 #[derive(Clone, Debug)]
 pub struct Chromosome {
     genes: Vec<f32>,
+    strategy: Vec<f32>,
 }
 
 impl PartialEq for Chromosome {
     fn eq(&self, other: &Self) -> bool {
         approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice())
+            && approx::relative_eq!(self.strategy.as_slice(), other.strategy.as_slice())
     }
 }
 
 impl Chromosome {
     pub fn new(genes: Vec<f32>) -> Self {
-        Self { genes }
+        Self {
+            genes,
+            strategy: Vec::new(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: Vec<f32>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn strategy(&self) -> &[f32] {
+        &self.strategy
+    }
+
+    pub fn strategy_mut(&mut self) -> &mut [f32] {
+        &mut self.strategy
+    }
+
+    /// Returns the child's strategy parameters - crossover methods deal with
+    /// genes only, so strategies are simply averaged (that's the so-called
+    /// intermediate recombination).
+    ///
+    /// When only one parent has got a strategy, the child inherits it as-is.
+    pub(crate) fn recombine_strategy(father: &Self, mother: &Self) -> Vec<f32> {
+        match (father.strategy.is_empty(), mother.strategy.is_empty()) {
+            (_, true) => father.strategy.clone(),
+            (true, false) => mother.strategy.clone(),
+            (false, false) => {
+                assert_eq!(
+                    father.strategy.len(),
+                    mother.strategy.len(),
+                    "parents have got different numbers of strategy parameters"
+                );
+
+                father
+                    .strategy
+                    .iter()
+                    .zip(&mother.strategy)
+                    .map(|(a, b)| (a + b) / 2.0)
+                    .collect()
+            }
+        }
     }
 
     pub fn len(&self) -> usize {
@@ -43,15 +95,13 @@ impl Index<usize> for Chromosome {
 
 impl FromIterator<f32> for Chromosome {
     fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
-        Self {
-            genes: iter.into_iter().collect(),
-        }
+        Self::new(iter.into_iter().collect())
     }
 }
 
 impl IntoIterator for Chromosome {
     type Item = f32;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
@@ -63,9 +113,7 @@ mod tests {
     use super::Chromosome;
 
     fn chromosome() -> Chromosome {
-        Chromosome {
-            genes: vec![0.0, 1.0, 2.0, 3.0],
-        }
+        Chromosome::new(vec![0.0, 1.0, 2.0, 3.0])
     }
 
     mod len {
@@ -146,4 +194,64 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    mod strategy {
+        use super::*;
+
+        #[test]
+        fn is_not_a_gene() {
+            let chromosome = chromosome().with_strategy(vec![0.5, 0.25]);
+
+            assert_eq!(chromosome.len(), 4);
+            assert_eq!(chromosome.strategy(), &[0.5, 0.25]);
+            assert_eq!(
+                chromosome.into_iter().collect::<Vec<_>>(),
+                vec![0.0, 1.0, 2.0, 3.0]
+            );
+        }
+
+        #[test]
+        fn eq() {
+            let chromosome = chromosome().with_strategy(vec![0.5]);
+
+            assert_eq!(chromosome, chromosome.clone());
+            assert_ne!(chromosome, self::chromosome());
+            assert_ne!(chromosome, self::chromosome().with_strategy(vec![0.25]));
+        }
+
+        #[test]
+        fn recombine() {
+            let father = chromosome().with_strategy(vec![0.5, 1.0]);
+            let mother = chromosome().with_strategy(vec![1.5, 0.0]);
+
+            assert_eq!(
+                Chromosome::recombine_strategy(&father, &mother),
+                vec![1.0, 0.5]
+            );
+        }
+
+        #[test]
+        fn recombine_with_one_strategy() {
+            let father = chromosome().with_strategy(vec![0.5, 1.0]);
+
+            assert_eq!(
+                Chromosome::recombine_strategy(&father, &chromosome()),
+                vec![0.5, 1.0]
+            );
+            assert_eq!(
+                Chromosome::recombine_strategy(&chromosome(), &father),
+                vec![0.5, 1.0]
+            );
+            assert!(Chromosome::recombine_strategy(&chromosome(), &chromosome()).is_empty());
+        }
+
+        #[test]
+        #[should_panic]
+        fn recombine_mismatched_strategies() {
+            let father = chromosome().with_strategy(vec![0.5, 1.0]);
+            let mother = chromosome().with_strategy(vec![0.5]);
+
+            Chromosome::recombine_strategy(&father, &mother);
+        }
+    }
 }
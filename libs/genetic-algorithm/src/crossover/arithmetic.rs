@@ -0,0 +1,75 @@
+use rand::RngCore;
+
+use crate::chromosome::Chromosome;
+
+use super::CrossoverMethod;
+
+/// Whole-arithmetic crossover: the child is a weighted average of its
+/// parents, with `weight` being the father's share.
+#[derive(Clone, Debug)]
+pub struct ArithmeticCrossover {
+    weight: f32,
+}
+
+impl ArithmeticCrossover {
+    pub fn new(weight: f32) -> Self {
+        assert!((0.0..=1.0).contains(&weight));
+        Self { weight }
+    }
+}
+
+impl Default for ArithmeticCrossover {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        father: &Chromosome,
+        mother: &Chromosome,
+        _rng: &mut dyn RngCore,
+    ) -> Chromosome {
+        father
+            .iter()
+            .zip(mother.iter())
+            .map(|(gene, mother_gene)| self.weight * gene + (1.0 - self.weight) * mother_gene)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{chromosome::Chromosome, crossover::CrossoverMethod};
+
+    use super::ArithmeticCrossover;
+
+    fn crossover(weight: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let father: Chromosome = [1.0, 2.0, -4.0].into_iter().collect();
+        let mother: Chromosome = [3.0, 2.0, 4.0].into_iter().collect();
+
+        ArithmeticCrossover::new(weight)
+            .crossover(&father, &mother, &mut rng)
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        assert_relative_eq!(crossover(0.5).as_slice(), [2.0, 2.0, 0.0].as_slice());
+        assert_relative_eq!(crossover(0.75).as_slice(), [1.5, 2.0, -2.0].as_slice());
+    }
+
+    #[test]
+    fn extremes() {
+        assert_relative_eq!(crossover(1.0).as_slice(), [1.0, 2.0, -4.0].as_slice());
+        assert_relative_eq!(crossover(0.0).as_slice(), [3.0, 2.0, 4.0].as_slice());
+    }
+}
@@ -0,0 +1,98 @@
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::CrossoverMethod;
+
+/// BLX-α: each gene is drawn uniformly from the range spanned by the
+/// parents' genes, extended by `alpha` times its width on both sides.
+///
+/// `alpha = 0.0` keeps children between their parents, while the commonly
+/// used `alpha = 0.5` lets the population keep its spread.
+#[derive(Clone, Debug)]
+pub struct BlendCrossover {
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn crossover(
+        &self,
+        father: &Chromosome,
+        mother: &Chromosome,
+        rng: &mut dyn RngCore,
+    ) -> Chromosome {
+        father
+            .iter()
+            .zip(mother.iter())
+            .map(|(&gene, &mother_gene)| {
+                let (min, max) = if gene < mother_gene {
+                    (gene, mother_gene)
+                } else {
+                    (mother_gene, gene)
+                };
+
+                let extent = self.alpha * (max - min);
+
+                rng.gen_range((min - extent)..=(max + extent))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{chromosome::Chromosome, crossover::CrossoverMethod};
+
+    use super::BlendCrossover;
+
+    fn crossover(alpha: f32) -> Chromosome {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let father: Chromosome = (0..100).map(|_| 1.0).collect();
+        let mother: Chromosome = (0..100).map(|_| 3.0).collect();
+
+        BlendCrossover::new(alpha).crossover(&father, &mother, &mut rng)
+    }
+
+    #[test]
+    fn without_alpha() {
+        let child = crossover(0.0);
+
+        assert_eq!(child.len(), 100);
+        assert!(child.iter().all(|gene| (1.0..=3.0).contains(gene)));
+    }
+
+    #[test]
+    fn with_alpha() {
+        let child = crossover(0.5);
+
+        assert!(child.iter().all(|gene| (0.0..=4.0).contains(gene)));
+
+        let outside = child
+            .iter()
+            .filter(|gene| !(1.0..=3.0).contains(*gene))
+            .count();
+
+        // Expected to be close to a half
+        assert_eq!(outside, 40);
+    }
+
+    #[test]
+    fn same_genes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent: Chromosome = [1.0, -2.0, 3.0].into_iter().collect();
+
+        let child = BlendCrossover::new(0.5).crossover(&parent, &parent, &mut rng);
+
+        assert_eq!(child, parent);
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+
+use rand::{seq::index, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::CrossoverMethod;
+
+/// Cuts both parents at `points` random places and takes the pieces
+/// alternately from the father and the mother.
+#[derive(Clone, Debug)]
+pub struct KPointCrossover {
+    points: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(points: usize) -> Self {
+        assert!(points > 0);
+        Self { points }
+    }
+
+    pub fn single_point() -> Self {
+        Self::new(1)
+    }
+}
+
+impl CrossoverMethod for KPointCrossover {
+    fn crossover(
+        &self,
+        father: &Chromosome,
+        mother: &Chromosome,
+        rng: &mut dyn RngCore,
+    ) -> Chromosome {
+        assert_eq!(father.len(), mother.len());
+
+        // Cutting before the first gene would be a no-op, so cuts are drawn
+        // from 1..len; when there are more points than that, we cut
+        // everywhere
+        let cuts = father.len().saturating_sub(1);
+        let mut points: Vec<_> = index::sample(rng, cuts, self.points.min(cuts))
+            .into_iter()
+            .map(|point| point + 1)
+            .collect();
+
+        points.sort_unstable();
+
+        let mut points = points.into_iter().peekable();
+        let mut from_father = true;
+
+        father
+            .iter()
+            .zip(mother.iter())
+            .enumerate()
+            .map(|(idx, (gene, mother_gene))| {
+                if points.next_if_eq(&idx).is_some() {
+                    from_father = !from_father;
+                }
+
+                if from_father {
+                    *gene
+                } else {
+                    *mother_gene
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{chromosome::Chromosome, crossover::CrossoverMethod};
+
+    use super::KPointCrossover;
+
+    fn parents() -> (Chromosome, Chromosome) {
+        let father = (1..=100).map(|n| n as f32).collect();
+        let mother = (1..=100).map(|n| -n as f32).collect();
+
+        (father, mother)
+    }
+
+    /// Returns the number of places where the child switches parents.
+    fn switches(child: &Chromosome) -> usize {
+        child
+            .iter()
+            .zip(child.iter().skip(1))
+            .filter(|(a, b)| a.signum() != b.signum())
+            .count()
+    }
+
+    #[test]
+    fn single_point() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (father, mother) = parents();
+
+        let child = KPointCrossover::single_point().crossover(&father, &mother, &mut rng);
+
+        assert_eq!(child.len(), 100);
+        assert_eq!(switches(&child), 1);
+        assert_eq!(child[0], father[0]);
+
+        let from_father = child.iter().filter(|gene| **gene > 0.0).count();
+        assert_eq!(from_father, 19);
+    }
+
+    #[test]
+    fn k_point() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (father, mother) = parents();
+
+        let child = KPointCrossover::new(4).crossover(&father, &mother, &mut rng);
+
+        assert_eq!(child.len(), 100);
+        assert_eq!(switches(&child), 4);
+        assert_eq!(child[0], father[0]);
+        assert_eq!(child[99], father[99]);
+
+        let from_father = child.iter().filter(|gene| **gene > 0.0).count();
+        assert_eq!(from_father, 41);
+    }
+
+    #[test]
+    fn more_points_than_genes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let father: Chromosome = [1.0, 2.0, 3.0].into_iter().collect();
+        let mother: Chromosome = [-1.0, -2.0, -3.0].into_iter().collect();
+
+        let child = KPointCrossover::new(10).crossover(&father, &mother, &mut rng);
+
+        assert_eq!(child, [1.0, -2.0, 3.0].into_iter().collect());
+    }
+}
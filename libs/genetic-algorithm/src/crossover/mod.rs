@@ -2,6 +2,10 @@ use rand::RngCore;
 
 use crate::chromosome::Chromosome;
 
+pub mod arithmetic;
+pub mod blend;
+pub mod k_point;
+pub mod simulated_binary;
 pub mod uniform;
 
 pub trait CrossoverMethod {
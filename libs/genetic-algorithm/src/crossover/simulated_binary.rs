@@ -0,0 +1,115 @@
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::CrossoverMethod;
+
+/// Simulated binary crossover (SBX): mimics how single-point crossover of
+/// binary-encoded genes spreads children around their parents.
+///
+/// The larger `eta` (the distribution index), the closer children stay to
+/// their parents; values between 2.0 and 20.0 are typical.
+#[derive(Clone, Debug)]
+pub struct SimulatedBinaryCrossover {
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0);
+        Self { eta }
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        father: &Chromosome,
+        mother: &Chromosome,
+        rng: &mut dyn RngCore,
+    ) -> Chromosome {
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        father
+            .iter()
+            .zip(mother.iter())
+            .map(|(&gene, &mother_gene)| {
+                let u: f32 = rng.gen();
+
+                let beta = if u <= 0.5 {
+                    num_traits::Float::powf(2.0 * u, exponent)
+                } else {
+                    num_traits::Float::powf(1.0 / (2.0 * (1.0 - u)), exponent)
+                };
+
+                // SBX yields two children, symmetric around their parents'
+                // mean - we pick one of them at random
+                let beta = if rng.gen_bool(0.5) { beta } else { -beta };
+
+                0.5 * ((1.0 + beta) * gene + (1.0 - beta) * mother_gene)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{chromosome::Chromosome, crossover::CrossoverMethod};
+
+    use super::SimulatedBinaryCrossover;
+
+    fn crossover(eta: f32) -> Chromosome {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let father: Chromosome = (0..1000).map(|_| 1.0).collect();
+        let mother: Chromosome = (0..1000).map(|_| 3.0).collect();
+
+        SimulatedBinaryCrossover::new(eta).crossover(&father, &mother, &mut rng)
+    }
+
+    fn between_parents(child: &Chromosome) -> usize {
+        child
+            .iter()
+            .filter(|gene| (1.0..=3.0).contains(*gene))
+            .count()
+    }
+
+    #[test]
+    fn test() {
+        let child = crossover(2.0);
+
+        assert_eq!(child.len(), 1000);
+
+        // Children are spread symmetrically around the parents' mean
+        let mean = child.iter().sum::<f32>() / 1000.0;
+        assert_relative_eq!(mean, 2.0, epsilon = 0.1);
+
+        // Half of the betas are below 1.0, i.e. between parents
+        assert_eq!(between_parents(&child), 495);
+    }
+
+    #[test]
+    fn larger_eta_stays_closer_to_parents() {
+        let spread = |child: &Chromosome| {
+            child
+                .iter()
+                .map(|gene| (gene - 1.0).abs().min((gene - 3.0).abs()))
+                .sum::<f32>()
+        };
+
+        assert!(spread(&crossover(20.0)) < spread(&crossover(2.0)));
+    }
+
+    #[test]
+    fn same_genes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent: Chromosome = [1.0, -2.0, 3.0].into_iter().collect();
+
+        let child = SimulatedBinaryCrossover::new(2.0).crossover(&parent, &parent, &mut rng);
+
+        assert_eq!(child, parent);
+    }
+}
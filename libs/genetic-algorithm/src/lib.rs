@@ -1,19 +1,31 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+
+use chromosome::Chromosome;
 use crossover::CrossoverMethod;
 use individual::Individual;
 use mutation::MutationMethod;
 use rand::RngCore;
+use replacement::Replacement;
 use select::SelectionMethod;
+use statistics::Statistics;
 
 pub mod chromosome;
 pub mod crossover;
 pub mod individual;
 pub mod mutation;
+pub mod replacement;
 pub mod select;
+pub mod statistics;
 
 pub struct GeneticAlgorithm<S> {
     selection_method: S,
     crossover_method: Box<dyn CrossoverMethod>,
     mutation_method: Box<dyn MutationMethod>,
+    replacement: Replacement,
 }
 
 impl<S> GeneticAlgorithm<S>
@@ -29,21 +41,85 @@ where
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            replacement: Replacement::default(),
         }
     }
 
-    pub fn evolve<I>(&self, population: &[I], rng: &mut dyn RngCore) -> Vec<I>
+    pub fn with_replacement(mut self, replacement: Replacement) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Returns the next generation, along with statistics of the current
+    /// one.
+    ///
+    /// Panics for replacements that rank offspring (see
+    /// `Replacement::ranks_offspring()`) - use `evolve_with()` for them.
+    pub fn evolve<I>(&self, population: &[I], rng: &mut dyn RngCore) -> (Vec<I>, Statistics)
+    where
+        I: Individual,
+    {
+        assert!(
+            !self.replacement.ranks_offspring(),
+            "{:?} needs evaluated offspring - use evolve_with()",
+            self.replacement
+        );
+
+        self.evolve_with(population, rng, |_| {})
+    }
+
+    /// Like `evolve()`, but calls `evaluate` on each child before the
+    /// replacement - so that e.g. (μ+λ) can tell how good the children are.
+    pub fn evolve_with<I>(
+        &self,
+        population: &[I],
+        rng: &mut dyn RngCore,
+        mut evaluate: impl FnMut(&mut I),
+    ) -> (Vec<I>, Statistics)
     where
         I: Individual,
     {
-        (0..population.len())
+        let count = self.replacement.offspring(population.len());
+        let mut offspring = self.breed(population, count, rng);
+
+        offspring.iter_mut().for_each(&mut evaluate);
+
+        (
+            self.replacement.replace(population, offspring),
+            Statistics::new(population),
+        )
+    }
+
+    fn breed<I>(&self, population: &[I], count: usize, rng: &mut dyn RngCore) -> Vec<I>
+    where
+        I: Individual,
+    {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let mut parents = S::BATCHED.then(|| {
+            self.selection_method
+                .select_many(population, 2 * count, rng)
+                .into_iter()
+        });
+
+        (0..count)
             .map(|_| {
-                let father = self.selection_method.select(population, rng);
-                let mother = self.selection_method.select(population, rng);
+                let (father, mother) = match &mut parents {
+                    Some(parents) => (parents.next().unwrap(), parents.next().unwrap()),
+                    None => (
+                        self.selection_method.select(population, rng),
+                        self.selection_method.select(population, rng),
+                    ),
+                };
 
-                let mut child =
-                    self.crossover_method
-                        .crossover(father.chromosome(), mother.chromosome(), rng);
+                let (father, mother) = (father.chromosome(), mother.chromosome());
+
+                let mut child = self
+                    .crossover_method
+                    .crossover(father, mother, rng)
+                    .with_strategy(Chromosome::recombine_strategy(father, mother));
 
                 self.mutation_method.mutate(&mut child, rng);
 
@@ -62,8 +138,13 @@ mod tests {
     use crate::{
         chromosome::Chromosome,
         crossover::uniform::UniformCrossover,
-        mutation::gaussian::GaussianMutation,
-        select::roulette_wheel::RouletteWheelSelection,
+        mutation::{self_adaptive::SelfAdaptiveMutation, uniform::UniformMutation},
+        replacement::Replacement,
+        select::{
+            roulette_wheel::RouletteWheelSelection,
+            stochastic_universal_sampling::StochasticUniversalSampling,
+            tournament::TournamentSelection,
+        },
         GeneticAlgorithm,
     };
 
@@ -110,7 +191,7 @@ mod tests {
         let ga = GeneticAlgorithm::new(
             RouletteWheelSelection::new(),
             UniformCrossover::new(),
-            GaussianMutation::new(0.5, 0.5),
+            UniformMutation::new(0.5, 0.5),
         );
 
         let mut polulation = vec![
@@ -121,7 +202,7 @@ mod tests {
         ];
 
         for _ in 0..10 {
-            polulation = ga.evolve(&polulation, &mut rng);
+            (polulation, _) = ga.evolve(&polulation, &mut rng);
         }
 
         let expected_population = vec![
@@ -133,4 +214,129 @@ mod tests {
 
         assert_eq!(polulation, expected_population);
     }
+
+    #[test]
+    fn elitism() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            // Strong mutations can make the fitness negative
+            TournamentSelection::new(2),
+            UniformCrossover::new(),
+            UniformMutation::new(1.0, 10.0),
+        )
+        .with_replacement(Replacement::Elitism { elites: 1 });
+
+        let mut population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        for _ in 0..10 {
+            let statistics;
+            (population, statistics) = ga.evolve(&population, &mut rng);
+
+            assert_eq!(population.len(), 4);
+            assert_eq!(population[0].fitness(), statistics.max_fitness());
+        }
+    }
+
+    #[test]
+    fn self_adaptation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2),
+            UniformCrossover::new(),
+            SelfAdaptiveMutation::new(),
+        );
+
+        let mut population: Vec<_> = (0..4)
+            .map(|n| {
+                TestIndividual::create(
+                    Chromosome::new(vec![n as f32; 3]).with_strategy(vec![0.5; 3]),
+                )
+            })
+            .collect();
+
+        for _ in 0..10 {
+            (population, _) = ga.evolve(&population, &mut rng);
+        }
+
+        for individual in &population {
+            let strategy = individual.chromosome().strategy();
+
+            assert_eq!(strategy.len(), 3);
+            assert!(strategy.iter().all(|step| *step != 0.5));
+        }
+    }
+
+    #[test]
+    fn batched_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            StochasticUniversalSampling::new(),
+            UniformCrossover::new(),
+            UniformMutation::new(0.0, 0.0),
+        );
+
+        // Only the last individual has a non-zero fitness, so SUS has to
+        // pick it as both parents of every child
+        let population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let (population, _) = ga.evolve(&population, &mut rng);
+
+        assert_eq!(population, vec![individual(&[1.0, 2.0, 4.0]); 3]);
+    }
+
+    mod plus_selection {
+        use super::*;
+
+        fn ga() -> GeneticAlgorithm<TournamentSelection> {
+            GeneticAlgorithm::new(
+                TournamentSelection::new(2),
+                UniformCrossover::new(),
+                UniformMutation::new(0.5, 0.5),
+            )
+            .with_replacement(Replacement::Plus { offspring: 8 })
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            vec![
+                individual(&[0.0, 0.0, 0.0]),
+                individual(&[1.0, 1.0, 1.0]),
+                individual(&[1.0, 2.0, 1.0]),
+                individual(&[1.0, 2.0, 4.0]),
+            ]
+        }
+
+        #[test]
+        fn evaluates_offspring() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut evaluated = 0;
+
+            let (population, _) = ga().evolve_with(&population(), &mut rng, |_| evaluated += 1);
+
+            assert_eq!(evaluated, 8);
+            assert_eq!(population.len(), 4);
+
+            // Parents compete with children, so the best can't get worse
+            assert!(population[0].fitness() >= 7.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn needs_evaluation() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            ga().evolve(&population(), &mut rng);
+        }
+    }
 }
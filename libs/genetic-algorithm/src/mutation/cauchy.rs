@@ -0,0 +1,76 @@
+use rand::{Rng, RngCore};
+use rand_distr::{Cauchy, Distribution};
+
+use crate::chromosome::Chromosome;
+
+use super::MutationMethod;
+
+/// With probability `chance`, nudges each gene by a value drawn from the
+/// Cauchy distribution centered at zero.
+///
+/// Compared to `NormalMutation`, most nudges are small, but once in a
+/// while there's a really large one - which helps to escape local optima.
+pub struct CauchyMutation {
+    chance: f32,
+    cauchy: Cauchy<f32>,
+}
+
+impl CauchyMutation {
+    pub fn new(chance: f32, scale: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(scale > 0.0);
+
+        Self {
+            chance,
+            cauchy: Cauchy::new(0.0, scale).unwrap(),
+        }
+    }
+}
+
+impl MutationMethod for CauchyMutation {
+    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
+        for gene in chromosome.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += self.cauchy.sample(rng);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        chromosome::Chromosome,
+        mutation::{cauchy::CauchyMutation, MutationMethod},
+    };
+
+    fn mutate(chance: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut chromosome: Chromosome = (0..1000).map(|_| 0.0).collect();
+
+        CauchyMutation::new(chance, 1.0).mutate(&mut chromosome, &mut rng);
+
+        chromosome.iter().copied().collect()
+    }
+
+    #[test]
+    fn zero_chance() {
+        assert!(mutate(0.0).iter().all(|gene| *gene == 0.0));
+    }
+
+    #[test]
+    fn has_heavy_tails() {
+        let genes = mutate(1.0);
+
+        // Half of the samples lie within one scale, like for N(0, 1.5²)...
+        let within_scale = genes.iter().filter(|gene| gene.abs() <= 1.0).count();
+        assert_eq!(within_scale, 477);
+
+        // ... but there's way more outliers: N(0, 1.5²) would give ~0
+        let outliers = genes.iter().filter(|gene| gene.abs() > 10.0).count();
+        assert_eq!(outliers, 59);
+    }
+}
@@ -0,0 +1,153 @@
+//! Mutation methods built out of other mutation methods.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::MutationMethod;
+
+/// Applies all of its mutations, one after another.
+#[derive(Default)]
+pub struct ChainMutation {
+    methods: Vec<Box<dyn MutationMethod>>,
+}
+
+impl ChainMutation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, method: impl MutationMethod + 'static) -> Self {
+        self.methods.push(Box::new(method));
+        self
+    }
+}
+
+impl MutationMethod for ChainMutation {
+    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
+        for method in &self.methods {
+            method.mutate(chromosome, rng);
+        }
+    }
+}
+
+/// Applies one of its mutations, picked at random (proportionally to the
+/// weights) separately for each chromosome.
+#[derive(Default)]
+pub struct ChoiceMutation {
+    methods: Vec<Box<dyn MutationMethod>>,
+    weights: Vec<f32>,
+}
+
+impl ChoiceMutation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn or(mut self, weight: f32, method: impl MutationMethod + 'static) -> Self {
+        assert!(weight >= 0.0);
+
+        self.methods.push(Box::new(method));
+        self.weights.push(weight);
+        self
+    }
+}
+
+impl MutationMethod for ChoiceMutation {
+    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
+        let method = WeightedIndex::new(&self.weights).expect("got no mutations to choose from");
+
+        self.methods[method.sample(rng)].mutate(chromosome, rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    /// Adds a constant to every gene.
+    struct Shift(f32);
+
+    impl MutationMethod for Shift {
+        fn mutate(&self, chromosome: &mut Chromosome, _rng: &mut dyn RngCore) {
+            for gene in chromosome.iter_mut() {
+                *gene += self.0;
+            }
+        }
+    }
+
+    /// Doubles every gene.
+    struct Double;
+
+    impl MutationMethod for Double {
+        fn mutate(&self, chromosome: &mut Chromosome, _rng: &mut dyn RngCore) {
+            for gene in chromosome.iter_mut() {
+                *gene *= 2.0;
+            }
+        }
+    }
+
+    mod chain {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut chromosome = Chromosome::new(vec![0.0, 1.0]);
+
+            ChainMutation::new()
+                .then(Shift(1.0))
+                .then(Double)
+                .mutate(&mut chromosome, &mut rng);
+
+            assert_eq!(chromosome, Chromosome::new(vec![2.0, 4.0]));
+        }
+
+        #[test]
+        fn empty() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut chromosome = Chromosome::new(vec![0.0, 1.0]);
+
+            ChainMutation::new().mutate(&mut chromosome, &mut rng);
+
+            assert_eq!(chromosome, Chromosome::new(vec![0.0, 1.0]));
+        }
+    }
+
+    mod choice {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = ChoiceMutation::new()
+                .or(3.0, Shift(1.0))
+                .or(1.0, Shift(-1.0));
+
+            let shifted_up = (0..1000)
+                .filter(|_| {
+                    let mut chromosome = Chromosome::new(vec![0.0]);
+                    mutation.mutate(&mut chromosome, &mut rng);
+                    chromosome[0] > 0.0
+                })
+                .count();
+
+            // Expected to be close to 750
+            assert_eq!(shifted_up, 771);
+        }
+
+        #[test]
+        #[should_panic]
+        fn empty() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut chromosome = Chromosome::new(vec![0.0]);
+
+            ChoiceMutation::new().mutate(&mut chromosome, &mut rng);
+        }
+    }
+}
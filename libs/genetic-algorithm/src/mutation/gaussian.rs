@@ -1,166 +1,35 @@
-use rand::{Rng, RngCore};
+//! `GaussianMutation` used to be the name of what's now `UniformMutation` -
+//! despite the name, it never drew from the normal distribution (that's
+//! `NormalMutation`).
 
-use crate::chromosome::Chromosome;
+use super::uniform::UniformMutation;
 
-use super::MutationMethod;
-
-pub struct GaussianMutation {
-    chance: f32,
-    coeff: f32,
-}
-
-impl GaussianMutation {
-    pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0);
-        Self { chance, coeff }
-    }
-}
-
-impl MutationMethod for GaussianMutation {
-    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
-        chromosome.iter_mut().for_each(|gene| {
-            let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
-            if rng.gen_bool(self.chance as f64) {
-                *gene += sign * rng.gen::<f32>() * self.coeff;
-            }
-        });
-    }
-}
+#[deprecated(note = "use `UniformMutation` (same behavior) or `NormalMutation` (actual Gaussian)")]
+pub type GaussianMutation = UniformMutation;
 
 #[cfg(test)]
 mod tests {
-    mod given_zero_chance {
-        mod and_zero_coeff {
-            use rand::SeedableRng;
-            use rand_chacha::ChaCha8Rng;
-
-            use crate::{
-                chromosome::Chromosome,
-                mutation::{gaussian::GaussianMutation, MutationMethod},
-            };
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let mut rng = ChaCha8Rng::from_seed(Default::default());
-                let mut chromosome = Chromosome::new(vec![0.0, 1.0, 2.0, 3.0]);
-                let original = chromosome.clone();
-
-                let mutation = GaussianMutation::new(0.0, 0.0);
-                mutation.mutate(&mut chromosome, &mut rng);
-
-                assert_eq!(chromosome, original);
-            }
-        }
-
-        mod and_nonzero_coeff {
-
-            use rand::SeedableRng;
-            use rand_chacha::ChaCha8Rng;
-
-            use crate::{
-                chromosome::Chromosome,
-                mutation::{gaussian::GaussianMutation, MutationMethod},
-            };
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let mut rng = ChaCha8Rng::from_seed(Default::default());
-                let mut chromosome = Chromosome::new(vec![0.0, 1.0, 2.0, 3.0]);
-                let original = chromosome.clone();
-
-                let mutation = GaussianMutation::new(0.0, 0.9);
-                mutation.mutate(&mut chromosome, &mut rng);
-
-                assert_eq!(chromosome, original);
-            }
-        }
-    }
-
-    mod given_fifty_fifty_chance {
-        mod and_zero_coeff {
-            use crate::chromosome::Chromosome;
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                use rand::SeedableRng;
-                use rand_chacha::ChaCha8Rng;
-
-                use crate::mutation::{gaussian::GaussianMutation, MutationMethod};
-
-                let mut rng = ChaCha8Rng::from_seed(Default::default());
-                let mut chromosome = Chromosome::new(vec![0.0, 1.0, 2.0, 3.0]);
-                let original = chromosome.clone();
-
-                let mutation = GaussianMutation::new(0.5, 0.0);
-                mutation.mutate(&mut chromosome, &mut rng);
-
-                assert_eq!(chromosome, original);
-            }
-        }
-
-        mod and_nonzero_coeff {
-            use crate::chromosome::Chromosome;
-
-            #[test]
-            fn slightly_changes_the_original_chromosome() {
-                use rand::SeedableRng;
-                use rand_chacha::ChaCha8Rng;
-
-                use crate::mutation::{gaussian::GaussianMutation, MutationMethod};
-
-                let mut rng = ChaCha8Rng::from_seed(Default::default());
-                let mut chromosome = Chromosome::new(vec![0.0, 1.0, 2.0, 3.0]);
-                let original = chromosome.clone();
-
-                let mutation = GaussianMutation::new(0.5, 0.9);
-                mutation.mutate(&mut chromosome, &mut rng);
-
-                assert_ne!(chromosome, original);
-            }
-        }
-    }
-
-    mod given_max_chance {
-        mod and_zero_coeff {
-            use crate::chromosome::Chromosome;
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                use rand::SeedableRng;
-                use rand_chacha::ChaCha8Rng;
-
-                use crate::mutation::{gaussian::GaussianMutation, MutationMethod};
-
-                let mut rng = ChaCha8Rng::from_seed(Default::default());
-                let mut chromosome = Chromosome::new(vec![0.0, 1.0, 2.0, 3.0]);
-                let original = chromosome.clone();
-
-                let mutation = GaussianMutation::new(1.0, 0.0);
-                mutation.mutate(&mut chromosome, &mut rng);
-
-                assert_eq!(chromosome, original);
-            }
-        }
-
-        mod and_nonzero_coeff {
-            use crate::chromosome::Chromosome;
+    #![allow(deprecated)]
 
-            #[test]
-            fn entirely_changes_the_original_chromosome() {
-                use rand::SeedableRng;
-                use rand_chacha::ChaCha8Rng;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
-                use crate::mutation::{gaussian::GaussianMutation, MutationMethod};
+    use super::*;
+    use crate::{chromosome::Chromosome, mutation::MutationMethod};
 
-                let mut rng = ChaCha8Rng::from_seed(Default::default());
-                let mut chromosome = Chromosome::new(vec![0.0, 1.0, 2.0, 3.0]);
-                let original = chromosome.clone();
+    #[test]
+    fn behaves_like_uniform_mutation() {
+        let mutate = |mutation: &dyn MutationMethod| {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut chromosome: Chromosome = (0..100).map(|_| 0.0).collect();
 
-                let mutation = GaussianMutation::new(1.0, 0.9);
-                mutation.mutate(&mut chromosome, &mut rng);
+            mutation.mutate(&mut chromosome, &mut rng);
+            chromosome
+        };
 
-                assert_ne!(chromosome, original);
-            }
-        }
+        assert_eq!(
+            mutate(&GaussianMutation::new(0.5, 0.3)),
+            mutate(&UniformMutation::new(0.5, 0.3))
+        );
     }
 }
@@ -2,7 +2,13 @@ use rand::RngCore;
 
 use crate::chromosome::Chromosome;
 
+pub mod cauchy;
+pub mod combine;
 pub mod gaussian;
+pub mod normal;
+pub mod polynomial;
+pub mod self_adaptive;
+pub mod uniform;
 
 pub trait MutationMethod {
     fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore);
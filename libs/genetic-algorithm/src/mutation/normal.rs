@@ -0,0 +1,93 @@
+use rand::{Rng, RngCore};
+use rand_distr::{Distribution, Normal};
+
+use crate::chromosome::Chromosome;
+
+use super::MutationMethod;
+
+/// With probability `chance`, nudges each gene by a value drawn from the
+/// normal distribution N(0, `sigma`²).
+pub struct NormalMutation {
+    chance: f32,
+    normal: Normal<f32>,
+}
+
+impl NormalMutation {
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(sigma >= 0.0);
+
+        Self {
+            chance,
+            normal: Normal::new(0.0, sigma).unwrap(),
+        }
+    }
+}
+
+impl MutationMethod for NormalMutation {
+    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
+        for gene in chromosome.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += self.normal.sample(rng);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        chromosome::Chromosome,
+        mutation::{normal::NormalMutation, MutationMethod},
+    };
+
+    fn mutate(chance: f32, sigma: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut chromosome: Chromosome = (0..1000).map(|_| 0.0).collect();
+
+        NormalMutation::new(chance, sigma).mutate(&mut chromosome, &mut rng);
+
+        chromosome.iter().copied().collect()
+    }
+
+    #[test]
+    fn zero_chance() {
+        assert!(mutate(0.0, 1.0).iter().all(|gene| *gene == 0.0));
+    }
+
+    #[test]
+    fn zero_sigma() {
+        assert!(mutate(1.0, 0.0).iter().all(|gene| *gene == 0.0));
+    }
+
+    #[test]
+    fn fifty_fifty_chance() {
+        let changed = mutate(0.5, 1.0)
+            .into_iter()
+            .filter(|gene| *gene != 0.0)
+            .count();
+
+        assert_eq!(changed, 495);
+    }
+
+    #[test]
+    fn follows_normal_distribution() {
+        let genes = mutate(1.0, 2.0);
+
+        let mean = genes.iter().sum::<f32>() / 1000.0;
+        let variance = genes.iter().map(|gene| (gene - mean).powi(2)).sum::<f32>() / 1000.0;
+
+        assert_relative_eq!(mean, 0.0, epsilon = 0.2);
+        assert_relative_eq!(variance.sqrt(), 2.0, epsilon = 0.2);
+
+        // ~68% of samples lie within one sigma, ~95% within two
+        let within = |n: f32| genes.iter().filter(|gene| gene.abs() <= n * 2.0).count();
+
+        assert_eq!(within(1.0), 676);
+        assert_eq!(within(2.0), 957);
+    }
+}
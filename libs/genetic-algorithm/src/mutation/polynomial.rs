@@ -0,0 +1,134 @@
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::MutationMethod;
+
+/// Deb's polynomial mutation, for genes bounded within `min..=max`.
+///
+/// Perturbations shrink as genes approach their bounds, so mutated genes
+/// never leave them; the larger `eta` (the distribution index), the
+/// smaller the perturbations - values between 20.0 and 100.0 are typical.
+pub struct PolynomialMutation {
+    chance: f32,
+    eta: f32,
+    min: f32,
+    max: f32,
+}
+
+impl PolynomialMutation {
+    pub fn new(chance: f32, eta: f32, min: f32, max: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(eta >= 0.0);
+        assert!(min < max);
+
+        Self {
+            chance,
+            eta,
+            min,
+            max,
+        }
+    }
+}
+
+impl MutationMethod for PolynomialMutation {
+    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
+        let range = self.max - self.min;
+        let exponent = 1.0 / (self.eta + 1.0);
+        let pow = num_traits::Float::powf;
+
+        for gene in chromosome.iter_mut() {
+            if !rng.gen_bool(self.chance as f64) {
+                continue;
+            }
+
+            let x = gene.clamp(self.min, self.max);
+            let u: f32 = rng.gen();
+
+            let delta = if u < 0.5 {
+                let below = (x - self.min) / range;
+                let value = 2.0 * u + (1.0 - 2.0 * u) * pow(1.0 - below, self.eta + 1.0);
+
+                pow(value, exponent) - 1.0
+            } else {
+                let above = (self.max - x) / range;
+                let value = 2.0 * (1.0 - u) + 2.0 * (u - 0.5) * pow(1.0 - above, self.eta + 1.0);
+
+                1.0 - pow(value, exponent)
+            };
+
+            *gene = (x + delta * range).clamp(self.min, self.max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        chromosome::Chromosome,
+        mutation::{polynomial::PolynomialMutation, MutationMethod},
+    };
+
+    fn mutate(mutation: PolynomialMutation, chromosome: &mut Chromosome) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        mutation.mutate(chromosome, &mut rng);
+    }
+
+    #[test]
+    fn zero_chance() {
+        let mut chromosome = Chromosome::new(vec![-0.5, 0.0, 0.5]);
+        let original = chromosome.clone();
+
+        mutate(
+            PolynomialMutation::new(0.0, 20.0, -1.0, 1.0),
+            &mut chromosome,
+        );
+
+        assert_eq!(chromosome, original);
+    }
+
+    #[test]
+    fn stays_within_bounds() {
+        let mut chromosome: Chromosome = (0..1000).map(|n| (n % 3) as f32 - 1.0).collect();
+        let original = chromosome.clone();
+
+        mutate(
+            PolynomialMutation::new(1.0, 1.0, -1.0, 1.0),
+            &mut chromosome,
+        );
+
+        assert_ne!(chromosome, original);
+        assert!(chromosome.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+    }
+
+    #[test]
+    fn larger_eta_gives_smaller_perturbations() {
+        let perturbation = |eta| {
+            let mut chromosome: Chromosome = (0..1000).map(|_| 0.0).collect();
+            mutate(
+                PolynomialMutation::new(1.0, eta, -1.0, 1.0),
+                &mut chromosome,
+            );
+            chromosome.iter().map(|gene| gene.abs()).sum::<f32>()
+        };
+
+        assert!(perturbation(100.0) < perturbation(20.0));
+        assert!(perturbation(20.0) < perturbation(1.0));
+    }
+
+    #[test]
+    fn clamps_genes_out_of_bounds() {
+        let mut chromosome = Chromosome::new(vec![-5.0, 5.0]);
+
+        mutate(
+            PolynomialMutation::new(1.0, 20.0, -1.0, 1.0),
+            &mut chromosome,
+        );
+
+        assert!(chromosome.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+    }
+}
@@ -0,0 +1,181 @@
+use rand::RngCore;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::chromosome::Chromosome;
+
+use super::MutationMethod;
+
+/// Evolution strategies' mutation, where step sizes aren't fixed, but
+/// carried in the chromosome's strategy parameters and evolved along with
+/// the genes:
+///
+/// - a single step size is shared by all genes,
+/// - one step size per gene lets each of them change at its own pace.
+///
+/// Step sizes get log-normal updates first (so that they stay positive),
+/// and then each gene is nudged by N(0, its step size²).
+///
+/// Panics if the chromosome has any other number of strategy parameters -
+/// so e.g. the initial population should be created with
+/// `Chromosome::with_strategy()`.
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    min_step: f32,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new() -> Self {
+        Self { min_step: 1e-4 }
+    }
+
+    /// Sets the lower bound for step sizes, which keeps the population from
+    /// freezing once step sizes get tiny.
+    pub fn with_min_step(mut self, min_step: f32) -> Self {
+        assert!(min_step >= 0.0);
+
+        self.min_step = min_step;
+        self
+    }
+}
+
+impl Default for SelfAdaptiveMutation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, chromosome: &mut Chromosome, rng: &mut dyn RngCore) {
+        let sqrt = num_traits::Float::sqrt;
+        let exp = num_traits::Float::exp;
+
+        let genes = chromosome.len();
+        let steps = chromosome.strategy().len();
+        let mut normal = || -> f32 { StandardNormal.sample(rng) };
+
+        assert!(
+            steps == 1 || steps == genes,
+            "expected 1 or {} strategy parameters, got {}",
+            genes,
+            steps
+        );
+
+        // Learning rates, as recommended by Schwefel
+        let n = genes as f32;
+
+        if steps == 1 {
+            let tau = 1.0 / sqrt(n);
+            let step = &mut chromosome.strategy_mut()[0];
+
+            *step = (*step * exp(tau * normal())).max(self.min_step);
+
+            let step = *step;
+
+            for gene in chromosome.iter_mut() {
+                *gene += step * normal();
+            }
+        } else {
+            let global_tau = 1.0 / sqrt(2.0 * n);
+            let local_tau = 1.0 / sqrt(2.0 * sqrt(n));
+            let global = global_tau * normal();
+
+            for step in chromosome.strategy_mut() {
+                *step = (*step * exp(global + local_tau * normal())).max(self.min_step);
+            }
+
+            let steps = chromosome.strategy().to_vec();
+
+            for (gene, step) in chromosome.iter_mut().zip(steps) {
+                *gene += step * normal();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        chromosome::Chromosome,
+        mutation::{self_adaptive::SelfAdaptiveMutation, MutationMethod},
+    };
+
+    fn chromosome(strategy: Vec<f32>) -> Chromosome {
+        Chromosome::new(vec![0.0; 100]).with_strategy(strategy)
+    }
+
+    #[test]
+    fn single_step() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut chromosome = chromosome(vec![0.5]);
+
+        SelfAdaptiveMutation::new().mutate(&mut chromosome, &mut rng);
+
+        let step = chromosome.strategy()[0];
+
+        assert_eq!(chromosome.len(), 100);
+        assert_ne!(step, 0.5);
+        assert!(step > 0.0);
+        assert!(chromosome.iter().all(|gene| *gene != 0.0));
+
+        // Genes are spread according to the updated step, so ~68% of them
+        // should lie within it
+        let within = chromosome.iter().filter(|gene| gene.abs() <= step).count();
+
+        assert_eq!(within, 67);
+    }
+
+    #[test]
+    fn step_per_gene() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut chromosome = chromosome(vec![0.5; 100]);
+
+        SelfAdaptiveMutation::new().mutate(&mut chromosome, &mut rng);
+
+        let strategy = chromosome.strategy();
+
+        assert_eq!(strategy.len(), 100);
+        assert!(strategy.iter().all(|step| *step > 0.0));
+        assert!(strategy.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn min_step() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut chromosome = chromosome(vec![0.0; 100]);
+
+        SelfAdaptiveMutation::new()
+            .with_min_step(0.1)
+            .mutate(&mut chromosome, &mut rng);
+
+        assert!(chromosome.strategy().iter().all(|step| *step >= 0.1));
+    }
+
+    #[test]
+    fn steps_adapt() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mutation = SelfAdaptiveMutation::new();
+
+        let steps: Vec<_> = (0..10)
+            .map(|_| {
+                let mut chromosome = chromosome(vec![0.5]);
+                mutation.mutate(&mut chromosome, &mut rng);
+                chromosome.strategy()[0]
+            })
+            .collect();
+
+        assert!(steps.iter().any(|step| *step > 0.5));
+        assert!(steps.iter().any(|step| *step < 0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_strategy() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut chromosome = Chromosome::new(vec![0.0; 100]);
+
+        SelfAdaptiveMutation::new().mutate(&mut chromosome, &mut rng);
+    }
+}
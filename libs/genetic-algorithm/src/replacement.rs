@@ -0,0 +1,215 @@
+use alloc::vec::Vec;
+
+use crate::individual::Individual;
+
+/// Decides which individuals make it into the next generation.
+///
+/// Individuals carried over from the current generation are re-created
+/// from their chromosomes (through `Individual::create()`), so they keep
+/// their genes, but not whatever else they've accumulated.
+///
+/// The next generation always has the same size as the current one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Replacement {
+    /// The whole population gets replaced by offspring.
+    #[default]
+    Generational,
+
+    /// The best `elites` individuals are carried over unchanged, the rest
+    /// gets replaced by offspring.
+    Elitism { elites: usize },
+
+    /// Only the worst `replaced` individuals get replaced by offspring.
+    SteadyState { replaced: usize },
+
+    /// (μ+λ): `offspring` children are bred, and the best individuals out
+    /// of parents and children together survive.
+    ///
+    /// Children get ranked before they ever get to live, so they have to be
+    /// evaluated first - which is why this works only through
+    /// `GeneticAlgorithm::evolve_with()`.
+    Plus { offspring: usize },
+
+    /// (μ,λ): `offspring` children are bred, and the best of them survive;
+    /// `offspring` must be at least the population's size.
+    ///
+    /// Children get ranked before they ever get to live, so they have to be
+    /// evaluated first - which is why this works only through
+    /// `GeneticAlgorithm::evolve_with()`.
+    Comma { offspring: usize },
+}
+
+impl Replacement {
+    /// Returns whether this replacement ranks offspring, i.e. needs them to
+    /// be evaluated beforehand.
+    pub fn ranks_offspring(&self) -> bool {
+        matches!(self, Self::Plus { .. } | Self::Comma { .. })
+    }
+
+    /// Returns how many children should be bred for the next generation.
+    pub(crate) fn offspring(&self, population: usize) -> usize {
+        match *self {
+            Self::Generational => population,
+            Self::Elitism { elites } => population - elites.min(population),
+            Self::SteadyState { replaced } => replaced.min(population),
+            Self::Plus { offspring } => offspring,
+            Self::Comma { offspring } => {
+                assert!(
+                    offspring >= population,
+                    "(μ,λ) needs at least {} offspring, but got {}",
+                    population,
+                    offspring
+                );
+
+                offspring
+            }
+        }
+    }
+
+    pub(crate) fn replace<I>(&self, population: &[I], offspring: Vec<I>) -> Vec<I>
+    where
+        I: Individual,
+    {
+        let survivors = population.len() - offspring.len().min(population.len());
+
+        match self {
+            Self::Generational => offspring,
+
+            Self::Elitism { .. } | Self::SteadyState { .. } => {
+                let mut next: Vec<_> =
+                    best(population.iter().collect(), survivors, |x| x.fitness())
+                        .into_iter()
+                        .map(recreate)
+                        .collect();
+
+                next.extend(offspring);
+                next
+            }
+
+            Self::Plus { .. } => {
+                let mut next: Vec<_> = population.iter().map(recreate).collect();
+                next.extend(offspring);
+
+                best(next, population.len(), I::fitness)
+            }
+
+            Self::Comma { .. } => best(offspring, population.len(), I::fitness),
+        }
+    }
+}
+
+/// Returns `n` fittest individuals, fittest first; ties are resolved in
+/// favour of the earlier ones.
+fn best<T>(mut individuals: Vec<T>, n: usize, fitness: impl Fn(&T) -> f32) -> Vec<T> {
+    individuals.sort_by(|a, b| fitness(b).total_cmp(&fitness(a)));
+    individuals.truncate(n);
+    individuals
+}
+
+fn recreate<I>(individual: &I) -> I
+where
+    I: Individual,
+{
+    I::create(individual.chromosome().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TestIndividual;
+
+    fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::create([fitness].into_iter().collect()))
+            .collect()
+    }
+
+    fn fitnesses(population: &[TestIndividual]) -> Vec<f32> {
+        population
+            .iter()
+            .map(|individual| individual.fitness())
+            .collect()
+    }
+
+    fn replace(replacement: Replacement, offspring: &[f32]) -> Vec<f32> {
+        let current = population(&[2.0, 4.0, 1.0, 3.0]);
+
+        assert_eq!(replacement.offspring(current.len()), offspring.len());
+
+        fitnesses(&replacement.replace(&current, population(offspring)))
+    }
+
+    #[test]
+    fn generational() {
+        assert_eq!(
+            replace(Replacement::Generational, &[0.0, 0.5, 0.25, 0.75]),
+            [0.0, 0.5, 0.25, 0.75]
+        );
+    }
+
+    mod elitism {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                replace(Replacement::Elitism { elites: 1 }, &[0.0, 0.5, 0.25]),
+                [4.0, 0.0, 0.5, 0.25]
+            );
+        }
+
+        #[test]
+        fn no_elites() {
+            assert_eq!(
+                replace(Replacement::Elitism { elites: 0 }, &[0.0, 0.5, 0.25, 0.75]),
+                [0.0, 0.5, 0.25, 0.75]
+            );
+        }
+
+        #[test]
+        fn more_elites_than_individuals() {
+            assert_eq!(
+                replace(Replacement::Elitism { elites: 10 }, &[]),
+                [4.0, 3.0, 2.0, 1.0]
+            );
+        }
+    }
+
+    #[test]
+    fn steady_state() {
+        assert_eq!(
+            replace(Replacement::SteadyState { replaced: 2 }, &[0.0, 5.0]),
+            [4.0, 3.0, 0.0, 5.0]
+        );
+    }
+
+    #[test]
+    fn plus() {
+        assert_eq!(
+            replace(Replacement::Plus { offspring: 3 }, &[0.0, 5.0, 2.5]),
+            [5.0, 4.0, 3.0, 2.5]
+        );
+    }
+
+    mod comma {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                replace(
+                    Replacement::Comma { offspring: 6 },
+                    &[0.0, 5.0, 2.5, 0.5, 0.25, 1.0]
+                ),
+                [5.0, 2.5, 1.0, 0.5]
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn too_few_offspring() {
+            Replacement::Comma { offspring: 3 }.offspring(4);
+        }
+    }
+}
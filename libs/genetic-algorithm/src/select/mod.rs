@@ -1,11 +1,39 @@
+use alloc::vec::Vec;
+
 use rand::RngCore;
 
 use crate::individual::Individual;
 
+pub mod rank;
 pub mod roulette_wheel;
+pub mod stochastic_universal_sampling;
+pub mod tournament;
 
 pub trait SelectionMethod {
+    /// Whether the genetic algorithm should select all parents of the next
+    /// generation through a single `select_many()` call (e.g. the stochastic
+    /// universal sampling needs to see them all at once).
+    ///
+    /// Otherwise each pair of parents is `select()`-ed right before breeding
+    /// their child, which keeps the order of random draws (and so seeded
+    /// results) stable.
+    const BATCHED: bool = false;
+
     fn select<'a, I>(&self, population: &'a [I], rng: &mut dyn RngCore) -> &'a I
     where
         I: Individual;
+
+    /// Selects `count` individuals at once, e.g. all the parents of the next
+    /// generation; by default that's just `count` independent `select()`s.
+    fn select_many<'a, I>(
+        &self,
+        population: &'a [I],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        (0..count).map(|_| self.select(population, rng)).collect()
+    }
 }
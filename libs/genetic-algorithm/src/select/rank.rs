@@ -0,0 +1,213 @@
+use alloc::vec::Vec;
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
+
+use crate::individual::Individual;
+
+use super::SelectionMethod;
+
+/// Like `RouletteWheelSelection`, but the wheel is split according to
+/// individuals' ranks instead of their fitnesses - so only the order of
+/// fitnesses matters, and the selection pressure stays the same no matter
+/// how close (or zero) they are.
+#[derive(Clone, Debug)]
+pub struct RankSelection {
+    ranking: Ranking,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ranking {
+    /// The worst individual gets `2.0 - pressure` slices, the best one gets
+    /// `pressure` slices, and the ones in between are spread linearly.
+    ///
+    /// `pressure` ranges from 1.0 (everybody is equally likely to be
+    /// selected) to 2.0 (the worst one never gets selected).
+    Linear { pressure: f32 },
+
+    /// The best individual gets one slice, the second best gets `base`
+    /// slices, the third one `base²` and so on.
+    Exponential { base: f32 },
+}
+
+impl RankSelection {
+    pub fn linear(pressure: f32) -> Self {
+        assert!((1.0..=2.0).contains(&pressure));
+
+        Self {
+            ranking: Ranking::Linear { pressure },
+        }
+    }
+
+    pub fn exponential(base: f32) -> Self {
+        assert!(base > 0.0 && base <= 1.0);
+
+        Self {
+            ranking: Ranking::Exponential { base },
+        }
+    }
+
+    /// Returns the population sorted from the worst individual to the best
+    /// one, along with their weights.
+    fn wheel<'a, I>(&self, population: &'a [I]) -> (Vec<&'a I>, WeightedIndex<f32>)
+    where
+        I: Individual,
+    {
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| a.fitness().total_cmp(&b.fitness()));
+
+        let n = ranked.len();
+
+        let weights: Vec<f32> = match self.ranking {
+            Ranking::Linear { pressure } => (0..n)
+                .map(|rank| {
+                    let position = if n > 1 {
+                        rank as f32 / (n - 1) as f32
+                    } else {
+                        1.0
+                    };
+
+                    2.0 - pressure + 2.0 * (pressure - 1.0) * position
+                })
+                .collect(),
+
+            Ranking::Exponential { base } => {
+                let mut weights: Vec<_> = (0..n)
+                    .scan(1.0, |weight, _| {
+                        let current = *weight;
+                        *weight *= base;
+                        Some(current)
+                    })
+                    .collect();
+
+                weights.reverse();
+                weights
+            }
+        };
+
+        let wheel = WeightedIndex::new(weights).expect("got an empty population");
+
+        (ranked, wheel)
+    }
+}
+
+impl SelectionMethod for RankSelection {
+    // Building the wheel means sorting the population, so do it once per
+    // generation instead of once per parent
+    const BATCHED: bool = true;
+
+    fn select<'a, I>(&self, population: &'a [I], rng: &mut dyn RngCore) -> &'a I
+    where
+        I: Individual,
+    {
+        let (ranked, wheel) = self.wheel(population);
+
+        ranked[wheel.sample(rng)]
+    }
+
+    fn select_many<'a, I>(
+        &self,
+        population: &'a [I],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        let (ranked, wheel) = self.wheel(population);
+
+        (0..count).map(|_| ranked[wheel.sample(rng)]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        individual::Individual,
+        select::{rank::RankSelection, SelectionMethod},
+        tests::TestIndividual,
+    };
+
+    fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::new(fitness))
+            .collect()
+    }
+
+    fn histogram<'a>(selected: impl Iterator<Item = &'a TestIndividual>) -> BTreeMap<i32, i32> {
+        selected.map(|individual| individual.fitness() as i32).fold(
+            BTreeMap::new(),
+            |mut acc, x| {
+                *acc.entry(x).or_insert(0) += 1;
+                acc
+            },
+        )
+    }
+
+    // Shuffled on purpose, so that ranks don't match positions
+    const FITNESSES: [f32; 4] = [2.0, 0.0, 3.0, 1.0];
+
+    #[test]
+    fn linear() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&FITNESSES);
+        let method = RankSelection::linear(2.0);
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&population, &mut rng)));
+
+        // Expected to be close to 0, 1/6, 2/6 and 3/6
+        let expected_histogram = vec![(1, 164), (2, 337), (3, 499)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn linear_without_pressure() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&FITNESSES);
+        let method = RankSelection::linear(1.0);
+
+        let actual_histogram =
+            histogram(method.select_many(&population, 1000, &mut rng).into_iter());
+
+        let expected_histogram = vec![(0, 248), (1, 253), (2, 270), (3, 229)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn exponential() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&FITNESSES);
+        let method = RankSelection::exponential(0.5);
+
+        let actual_histogram =
+            histogram(method.select_many(&population, 1000, &mut rng).into_iter());
+
+        // Expected to be close to 1/15, 2/15, 4/15 and 8/15
+        let expected_histogram = vec![(0, 72), (1, 130), (2, 273), (3, 525)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn zero_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&[0.0, 0.0, 0.0]);
+
+        let selected = RankSelection::linear(1.5).select_many(&population, 10, &mut rng);
+
+        assert_eq!(selected.len(), 10);
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::vec::Vec;
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::individual::Individual;
+
+use super::SelectionMethod;
+
+/// Fitness-proportional selection, like `RouletteWheelSelection`, but all
+/// individuals are picked in a single spin of a wheel with evenly spaced
+/// pointers.
+///
+/// This way each individual gets selected either `floor(expected)` or
+/// `ceil(expected)` times, instead of being subject to the roulette's luck
+/// - which is why it's meant to be used through `select_many()`.
+///
+/// When the whole population has zero fitness, everybody is equally likely
+/// to be selected.
+#[derive(Clone, Debug, Default)]
+pub struct StochasticUniversalSampling;
+
+impl StochasticUniversalSampling {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for StochasticUniversalSampling {
+    const BATCHED: bool = true;
+
+    fn select<'a, I>(&self, population: &'a [I], rng: &mut dyn RngCore) -> &'a I
+    where
+        I: Individual,
+    {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many<'a, I>(
+        &self,
+        population: &'a [I],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        let fitness = |individual: &I| {
+            let fitness = individual.fitness();
+            assert!(fitness >= 0.0, "got a negative fitness: {}", fitness);
+            fitness
+        };
+
+        let total: f32 = population.iter().map(fitness).sum();
+
+        let weight = |individual: &I| {
+            if total > 0.0 {
+                fitness(individual)
+            } else {
+                1.0
+            }
+        };
+
+        let total = if total > 0.0 {
+            total
+        } else {
+            population.len() as f32
+        };
+
+        let step = total / count.max(1) as f32;
+        let mut pointer = rng.gen_range(0.0..step);
+        let mut selected = Vec::with_capacity(count);
+        let mut individuals = population.iter();
+        let mut current = individuals.next().unwrap();
+        let mut end = weight(current);
+
+        while selected.len() < count {
+            // Rounding errors can leave the last pointers just past the end
+            // of the wheel
+            while pointer >= end {
+                match individuals.next() {
+                    Some(next) => {
+                        current = next;
+                        end += weight(current);
+                    }
+                    None => break,
+                }
+            }
+
+            selected.push(current);
+            pointer += step;
+        }
+
+        // Otherwise consecutive parents would end up being neighbours
+        selected.shuffle(rng);
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        individual::Individual,
+        select::{stochastic_universal_sampling::StochasticUniversalSampling, SelectionMethod},
+        tests::TestIndividual,
+    };
+
+    fn histogram(fitnesses: &[f32], count: usize) -> BTreeMap<i32, i32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::new(fitness))
+            .collect();
+
+        StochasticUniversalSampling::new()
+            .select_many(&population, count, &mut rng)
+            .into_iter()
+            .map(|individual| individual.fitness() as i32)
+            .fold(BTreeMap::new(), |mut acc, x| {
+                *acc.entry(x).or_insert(0) += 1;
+                acc
+            })
+    }
+
+    #[test]
+    fn test() {
+        let actual_histogram = histogram(&[0.0, 1.0, 2.0, 3.0], 1000);
+
+        let expected_histogram = vec![(1, 167), (2, 333), (3, 500)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    /// Returns how many times each individual got selected.
+    fn counts(fitnesses: &[f32], count: usize) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::new(fitness))
+            .collect();
+
+        let selected = StochasticUniversalSampling::new().select_many(&population, count, &mut rng);
+
+        population
+            .iter()
+            .map(|individual| {
+                selected
+                    .iter()
+                    .filter(|selected| std::ptr::eq(**selected, individual))
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_individual_gets_its_share() {
+        let fitnesses = [1.0, 2.0, 3.0, 4.0];
+        let counts = counts(&fitnesses, 7);
+
+        assert_eq!(counts.iter().sum::<usize>(), 7);
+
+        // Expected counts are 0.7, 1.4, 2.1 and 2.8 - with SUS each
+        // individual gets either floor or ceil of that
+        for (fitness, count) in fitnesses.iter().zip(counts) {
+            let expected = fitness * 7.0 / 10.0;
+
+            assert!(
+                (expected.floor() as usize..=expected.ceil() as usize).contains(&count),
+                "fitness {} got selected {} times",
+                fitness,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn zero_fitness() {
+        assert_eq!(counts(&[0.0, 0.0, 0.0, 0.0], 8), [2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn shuffles_the_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = (0..10).map(|n| TestIndividual::new(n as f32)).collect();
+
+        let selected: Vec<_> = StochasticUniversalSampling::new()
+            .select_many(&population, 20, &mut rng)
+            .into_iter()
+            .map(|individual| individual.fitness())
+            .collect();
+
+        assert!(selected.windows(2).any(|pair| pair[0] > pair[1]));
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::vec::Vec;
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::individual::Individual;
+
+use super::SelectionMethod;
+
+/// Draws `size` random individuals (with replacement) and picks the fittest
+/// of them.
+///
+/// Contrary to `RouletteWheelSelection`, only the order of fitnesses
+/// matters - so it keeps working when most of the population has zero
+/// fitness, and isn't dominated by a single outlier.
+///
+/// With `probability` below 1.0 the fittest contestant wins only with that
+/// probability, otherwise the second one wins with that probability and so
+/// on - which lowers the selection pressure.
+#[derive(Clone, Debug)]
+pub struct TournamentSelection {
+    size: usize,
+    probability: f64,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+
+        Self {
+            size,
+            probability: 1.0,
+        }
+    }
+
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        assert!((0.0..=1.0).contains(&probability));
+
+        self.probability = probability;
+        self
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, I>(&self, population: &'a [I], rng: &mut dyn RngCore) -> &'a I
+    where
+        I: Individual,
+    {
+        let mut contestants: Vec<_> = (0..self.size)
+            .map(|_| population.choose(rng).expect("got an empty population"))
+            .collect();
+
+        contestants.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let (last, contestants) = contestants.split_last().unwrap();
+
+        contestants
+            .iter()
+            .find(|_| rng.gen_bool(self.probability))
+            .unwrap_or(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        individual::Individual,
+        select::{tournament::TournamentSelection, SelectionMethod},
+        tests::TestIndividual,
+    };
+
+    fn histogram(method: TournamentSelection, fitnesses: &[f32]) -> BTreeMap<i32, i32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::new(fitness))
+            .collect();
+
+        (0..1000)
+            .map(|_| method.select(&population, &mut rng).fitness() as i32)
+            .fold(BTreeMap::new(), |mut acc, x| {
+                *acc.entry(x).or_insert(0) += 1;
+                acc
+            })
+    }
+
+    #[test]
+    fn test() {
+        let actual_histogram = histogram(TournamentSelection::new(2), &[0.0, 1.0, 2.0, 3.0]);
+
+        let expected_histogram = vec![(0, 56), (1, 189), (2, 343), (3, 412)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn with_probability() {
+        let actual_histogram = histogram(
+            TournamentSelection::new(2).with_probability(0.75),
+            &[0.0, 1.0, 2.0, 3.0],
+        );
+
+        let expected_histogram = vec![(0, 161), (1, 214), (2, 292), (3, 333)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn single_contestant_is_uniform() {
+        let actual_histogram = histogram(TournamentSelection::new(1), &[0.0, 1.0, 2.0, 3.0]);
+
+        let expected_histogram = vec![(0, 246), (1, 262), (2, 254), (3, 238)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn zero_fitness() {
+        // Roulette would panic here
+        let actual_histogram = histogram(TournamentSelection::new(3), &[0.0, 0.0, 0.0, 2.0]);
+
+        let expected_histogram = vec![(0, 452), (2, 548)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+}
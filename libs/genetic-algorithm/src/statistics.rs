@@ -0,0 +1,188 @@
+use alloc::vec::Vec;
+
+use crate::individual::Individual;
+
+/// Summary of a population's fitness and genes.
+///
+/// `gene_diversity` is the standard deviation of each gene across the
+/// population, averaged over all genes - when it approaches zero, the
+/// population has converged (and only mutations can get it moving again).
+/// Strategy parameters aren't taken into account.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Statistics {
+    min_fitness: f32,
+    max_fitness: f32,
+    mean_fitness: f32,
+    median_fitness: f32,
+    stddev_fitness: f32,
+    zero_fitness: usize,
+    gene_diversity: f32,
+}
+
+impl Statistics {
+    pub fn new<I>(population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Self::default();
+        }
+
+        let mut fitnesses: Vec<_> = population
+            .iter()
+            .map(|individual| individual.fitness())
+            .collect();
+
+        fitnesses.sort_by(f32::total_cmp);
+
+        let n = fitnesses.len();
+
+        let median_fitness = if n % 2 == 0 {
+            (fitnesses[n / 2 - 1] + fitnesses[n / 2]) / 2.0
+        } else {
+            fitnesses[n / 2]
+        };
+
+        let (mean_fitness, stddev_fitness) = mean_and_stddev(fitnesses.iter().copied());
+
+        let genes = population
+            .iter()
+            .map(|individual| individual.chromosome().len())
+            .min()
+            .unwrap_or(0);
+
+        let gene_diversity = if genes > 0 {
+            let stddevs: f32 = (0..genes)
+                .map(|gene| {
+                    let values = population
+                        .iter()
+                        .map(|individual| individual.chromosome()[gene]);
+
+                    mean_and_stddev(values).1
+                })
+                .sum();
+
+            stddevs / genes as f32
+        } else {
+            0.0
+        };
+
+        Self {
+            min_fitness: fitnesses[0],
+            max_fitness: fitnesses[n - 1],
+            mean_fitness,
+            median_fitness,
+            stddev_fitness,
+            zero_fitness: fitnesses.iter().filter(|&&fitness| fitness == 0.0).count(),
+            gene_diversity,
+        }
+    }
+
+    pub fn min_fitness(&self) -> f32 {
+        self.min_fitness
+    }
+
+    pub fn max_fitness(&self) -> f32 {
+        self.max_fitness
+    }
+
+    pub fn mean_fitness(&self) -> f32 {
+        self.mean_fitness
+    }
+
+    pub fn median_fitness(&self) -> f32 {
+        self.median_fitness
+    }
+
+    pub fn stddev_fitness(&self) -> f32 {
+        self.stddev_fitness
+    }
+
+    /// Returns the number of individuals with zero fitness.
+    pub fn zero_fitness(&self) -> usize {
+        self.zero_fitness
+    }
+
+    pub fn gene_diversity(&self) -> f32 {
+        self.gene_diversity
+    }
+}
+
+/// Returns the mean and the (population) standard deviation.
+fn mean_and_stddev(values: impl Iterator<Item = f32> + Clone) -> (f32, f32) {
+    let n = values.clone().count() as f32;
+    let mean = values.clone().sum::<f32>() / n;
+    let variance = values
+        .map(|value| (value - mean) * (value - mean))
+        .sum::<f32>()
+        / n;
+
+    (mean, num_traits::Float::sqrt(variance))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::{chromosome::Chromosome, tests::TestIndividual};
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect::<Chromosome>())
+    }
+
+    #[test]
+    fn test() {
+        // Fitnesses are 6, 0, 2, 4
+        let population = [
+            individual(&[1.0, 2.0, 3.0]),
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 0.0, 1.0]),
+            individual(&[2.0, 2.0, 0.0]),
+        ];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.min_fitness(), 0.0);
+        assert_relative_eq!(stats.max_fitness(), 6.0);
+        assert_relative_eq!(stats.mean_fitness(), 3.0);
+        assert_relative_eq!(stats.median_fitness(), 3.0);
+        assert_relative_eq!(stats.stddev_fitness(), 5.0f32.sqrt());
+        assert_eq!(stats.zero_fitness(), 1);
+
+        // Per-gene stddevs are sqrt(0.5), 1.0 and sqrt(1.5)
+        assert_relative_eq!(
+            stats.gene_diversity(),
+            (0.5f32.sqrt() + 1.0 + 1.5f32.sqrt()) / 3.0
+        );
+    }
+
+    #[test]
+    fn odd_population() {
+        let population = [individual(&[5.0]), individual(&[1.0]), individual(&[0.0])];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.median_fitness(), 1.0);
+        assert_relative_eq!(stats.mean_fitness(), 2.0);
+    }
+
+    #[test]
+    fn converged_population() {
+        let population = [individual(&[1.0, -1.0]), individual(&[1.0, -1.0])];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.gene_diversity(), 0.0);
+        assert_relative_eq!(stats.stddev_fitness(), 0.0);
+        assert_eq!(stats.zero_fitness(), 2);
+    }
+
+    #[test]
+    fn empty_population() {
+        assert_eq!(
+            Statistics::new::<TestIndividual>(&[]),
+            Statistics::default()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+use crate::Float;
+
+#[derive(Clone, Default)]
+pub enum Activation {
+    #[default]
+    ReLU,
+    LeakyReLU(f32),
+    Tanh,
+    Sigmoid,
+    Identity,
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl Activation {
+    pub fn custom(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    pub fn apply<F: Float>(&self, x: F) -> F {
+        match self {
+            Self::ReLU => x.max(F::zero()),
+            Self::LeakyReLU(slope) => {
+                if x > F::zero() {
+                    x
+                } else {
+                    x * F::from_f32(*slope)
+                }
+            }
+            Self::Tanh => x.tanh(),
+            Self::Sigmoid => F::one() / (F::one() + (-x).exp()),
+            Self::Identity => x,
+            Self::Custom(f) => F::from_f32(f(x.into_f32())),
+        }
+    }
+
+    /// Returns the derivative at `x`, given `y = self.apply(x)`.
+    ///
+    /// Custom activations are differentiated numerically.
+    pub fn derivative<F: Float>(&self, x: F, y: F) -> F {
+        match self {
+            Self::ReLU => {
+                if x > F::zero() {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            }
+            Self::LeakyReLU(slope) => {
+                if x > F::zero() {
+                    F::one()
+                } else {
+                    F::from_f32(*slope)
+                }
+            }
+            Self::Tanh => F::one() - y * y,
+            Self::Sigmoid => y * (F::one() - y),
+            Self::Identity => F::one(),
+            Self::Custom(f) => {
+                const H: f32 = 1e-3;
+
+                let x = x.into_f32();
+                F::from_f32((f(x + H) - f(x - H)) / (2.0 * H))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Activation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReLU => write!(f, "ReLU"),
+            Self::LeakyReLU(slope) => f.debug_tuple("LeakyReLU").field(slope).finish(),
+            Self::Tanh => write!(f, "Tanh"),
+            Self::Sigmoid => write!(f, "Sigmoid"),
+            Self::Identity => write!(f, "Identity"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    mod apply {
+        use super::*;
+
+        #[test]
+        fn relu() {
+            assert_relative_eq!(Activation::ReLU.apply(-2.0f32), 0.0);
+            assert_relative_eq!(Activation::ReLU.apply(2.0f32), 2.0);
+        }
+
+        #[test]
+        fn leaky_relu() {
+            assert_relative_eq!(Activation::LeakyReLU(0.1).apply(-2.0f32), -0.2);
+            assert_relative_eq!(Activation::LeakyReLU(0.1).apply(2.0f32), 2.0);
+        }
+
+        #[test]
+        fn tanh() {
+            assert_relative_eq!(Activation::Tanh.apply(0.0f32), 0.0);
+            assert_relative_eq!(Activation::Tanh.apply(-1.0f32), -0.7615942);
+        }
+
+        #[test]
+        fn sigmoid() {
+            assert_relative_eq!(Activation::Sigmoid.apply(0.0f32), 0.5);
+            assert_relative_eq!(Activation::Sigmoid.apply(2.0f32), 0.8807971);
+        }
+
+        #[test]
+        fn identity() {
+            assert_relative_eq!(Activation::Identity.apply(-3.5f32), -3.5);
+        }
+
+        #[test]
+        fn custom() {
+            let activation = Activation::custom(|x| x * x);
+
+            assert_relative_eq!(activation.apply(-3.0f32), 9.0);
+        }
+    }
+
+    mod derivative {
+        use super::*;
+
+        fn check(activation: Activation, x: f32) {
+            const H: f32 = 1e-3;
+
+            let expected = (activation.apply(x + H) - activation.apply(x - H)) / (2.0 * H);
+            let actual = activation.derivative(x, activation.apply(x));
+
+            assert_relative_eq!(actual, expected, epsilon = 1e-3);
+        }
+
+        #[test]
+        fn test() {
+            for x in [-1.5, -0.2, 0.3, 2.0] {
+                check(Activation::ReLU, x);
+                check(Activation::LeakyReLU(0.1), x);
+                check(Activation::Tanh, x);
+                check(Activation::Sigmoid, x);
+                check(Activation::Identity, x);
+                check(Activation::custom(|x| x * x * x), x);
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use alloc::vec::Vec;
+
+use crate::{Float, Network};
+
+/// Scratch space for `Network::propagate_into()`.
+///
+/// A buffer created for one network can be reused for any other network
+/// of the same shape (or a narrower one) without allocating.
+#[derive(Clone, Debug, Default)]
+pub struct PropagationBuffer<F = f32> {
+    pub(crate) front: Vec<F>,
+    pub(crate) back: Vec<F>,
+}
+
+impl<F: Float> PropagationBuffer<F> {
+    pub fn new(network: &Network<F>) -> Self {
+        let mut this = Self::default();
+        this.fit(network.widest_layer());
+        this
+    }
+
+    /// Grows the buffer if `width` is more than what's been seen so far;
+    /// a no-op otherwise.
+    pub(crate) fn fit(&mut self, width: usize) {
+        if self.front.len() < width {
+            self.front.resize(width, F::zero());
+            self.back.resize(width, F::zero());
+        }
+    }
+
+    /// Returns a single scratch slice of exactly `len` numbers.
+    pub(crate) fn reserve(&mut self, len: usize) -> &mut [F] {
+        if self.front.len() < len {
+            self.front.resize(len, F::zero());
+        }
+
+        &mut self.front[..len]
+    }
+}
@@ -0,0 +1,87 @@
+use crate::Float;
+
+/// Continuous-time recurrent neurons, as in Beer's CTRNNs.
+///
+/// Each neuron keeps a potential `y` that follows
+///
+/// ```text
+/// tau * dy/dt = -y + sum(w * input) + sum(w_rec * activation(y' + bias'))
+/// ```
+///
+/// (`y'` and `bias'` being the layer's other neurons), integrated with the
+/// Euler method once per `propagate()` call; neuron's output is then
+/// `activation(y + bias)`.
+///
+/// Compared to `LayerKind::Recurrent`, outputs change gradually (at a pace
+/// given by the evolvable time constants), which makes for smooth motor
+/// signals and lets a few neurons form oscillators.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ctrnn {
+    /// Integration step, in the same units as the neurons' time constants.
+    pub dt: f32,
+}
+
+impl Ctrnn {
+    pub fn new(dt: f32) -> Self {
+        assert!(dt > 0.0);
+
+        Self { dt }
+    }
+
+    /// Returns which fraction of the way towards its target a neuron with
+    /// given time constant moves during a single step.
+    ///
+    /// Time constants come straight from the genes, so they're taken as
+    /// absolute values and can't get shorter than `dt` - otherwise Euler
+    /// integration would overshoot.
+    pub(crate) fn rate<F: Float>(&self, tau: F) -> F {
+        let dt = F::from_f32(self.dt);
+        dt / tau.abs().max(dt)
+    }
+
+    /// Derivative of `rate()` over `tau`.
+    pub(crate) fn rate_derivative<F: Float>(&self, tau: F) -> F {
+        let dt = F::from_f32(self.dt);
+
+        if tau.abs() > dt {
+            -dt / (tau * tau) * tau.signum()
+        } else {
+            F::zero()
+        }
+    }
+}
+
+impl Default for Ctrnn {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn rate() {
+        let ctrnn = Ctrnn::new(0.1);
+
+        assert_relative_eq!(ctrnn.rate(0.5f32), 0.2);
+        assert_relative_eq!(ctrnn.rate(-0.5f32), 0.2);
+        assert_relative_eq!(ctrnn.rate(0.01f32), 1.0);
+    }
+
+    #[test]
+    fn rate_derivative() {
+        const H: f32 = 1e-3;
+
+        let ctrnn = Ctrnn::new(0.1);
+
+        for tau in [-0.7f32, 0.3, 2.0] {
+            let expected = (ctrnn.rate(tau + H) - ctrnn.rate(tau - H)) / (2.0 * H);
+
+            assert_relative_eq!(ctrnn.rate_derivative(tau), expected, epsilon = 1e-2);
+        }
+    }
+}
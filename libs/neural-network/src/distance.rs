@@ -0,0 +1,311 @@
+//! Measuring how far apart networks are, and blending them - e.g. for
+//! tracking diversity of a population or following a lineage.
+//!
+//! All of these work only on networks of the same topology, that is with
+//! the same number of layers, neurons and layer kinds; activations aren't
+//! compared (custom ones can't be), and blended networks simply take them
+//! from the first network.
+
+use alloc::vec::Vec;
+
+use crate::{Float, Network, NetworkError};
+
+impl<F: Float> Network<F> {
+    /// Returns the Euclidean (L2) distance between weights of both networks.
+    pub fn distance(&self, other: &Self) -> Result<F, NetworkError> {
+        self.check_topology(other)?;
+
+        Ok(self
+            .weights()
+            .zip(other.weights())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<F>()
+            .sqrt())
+    }
+
+    /// Returns the root-mean-square difference between outputs of both
+    /// networks, over given probe inputs.
+    ///
+    /// Unlike `distance()`, this ignores differences that don't change the
+    /// behavior (e.g. weights of dead neurons or swapped hidden neurons).
+    pub fn functional_distance(&self, other: &Self, probes: &[Vec<F>]) -> Result<F, NetworkError> {
+        self.check_topology(other)?;
+
+        let errors: Vec<F> = self
+            .propagate_detached(probes)
+            .zip(other.propagate_detached(probes))
+            .flat_map(|(mut a, mut b)| {
+                let (a, b) = (a.pop().unwrap(), b.pop().unwrap());
+
+                a.into_iter().zip(b).map(|(a, b)| (a - b) * (a - b))
+            })
+            .collect();
+
+        if errors.is_empty() {
+            return Ok(F::zero());
+        }
+
+        let n = F::from(errors.len()).unwrap();
+
+        Ok((errors.into_iter().sum::<F>() / n).sqrt())
+    }
+
+    /// Returns a network whose weights lie at `t` of the way from this
+    /// network's towards `other`'s - i.e. `t = 0.0` yields a copy of this
+    /// network, and `t = 1.0` yields a copy of `other`.
+    ///
+    /// The result starts with a clean recurrent state and no connection
+    /// masks.
+    pub fn interpolate(&self, other: &Self, t: F) -> Result<Self, NetworkError> {
+        self.check_topology(other)?;
+
+        let weights = self
+            .weights()
+            .zip(other.weights())
+            .map(|(a, b)| a + (b - a) * t);
+
+        Self::try_from_weights(&self.topology(), weights)
+    }
+
+    /// Returns a network whose every weight is the mean of the
+    /// corresponding weights of `networks`.
+    ///
+    /// The result starts with a clean recurrent state and no connection
+    /// masks.
+    pub fn average(networks: &[Self]) -> Result<Self, NetworkError> {
+        let (first, rest) = networks.split_first().ok_or(NetworkError::NoNetworks)?;
+        let mut sums: Vec<_> = first.weights().collect();
+
+        for network in rest {
+            first.check_topology(network)?;
+
+            for (sum, weight) in sums.iter_mut().zip(network.weights()) {
+                *sum += weight;
+            }
+        }
+
+        let n = F::from(networks.len()).unwrap();
+
+        Self::try_from_weights(&first.topology(), sums.into_iter().map(|sum| sum / n))
+    }
+
+    fn check_topology(&self, other: &Self) -> Result<(), NetworkError> {
+        let (a, b) = (self.topology(), other.topology());
+
+        let layer = a
+            .iter()
+            .zip(&b)
+            .position(|(a, b)| a.neurons != b.neurons || a.kind != b.kind);
+
+        match layer {
+            Some(layer) => Err(NetworkError::TopologyMismatch { layer }),
+            None if a.len() != b.len() => Err(NetworkError::TopologyMismatch {
+                layer: a.len().min(b.len()),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::{Activation, LayerKind, LayerTopology};
+
+    fn layers() -> [LayerTopology; 3] {
+        [
+            LayerTopology::new(2),
+            LayerTopology::new(2).with_kind(LayerKind::Recurrent),
+            LayerTopology::new(1).with_activation(Activation::Identity),
+        ]
+    }
+
+    // 2 * (1 + 2 + 2) + (1 + 2)
+    fn network(weights: [f32; 13]) -> Network {
+        Network::from_weights(&layers(), weights)
+    }
+
+    fn a() -> Network {
+        network([
+            0.1, 0.5, -0.3, 0.2, 0.0, -0.1, 0.4, 0.9, -0.2, 0.3, 0.2, 1.0, -0.5,
+        ])
+    }
+
+    fn b() -> Network {
+        network([
+            0.1, 0.5, -0.3, 0.2, 0.0, -0.1, 0.4, 0.9, -0.2, 0.3, 0.6, 1.0, -0.8,
+        ])
+    }
+
+    fn other_topology() -> Network {
+        Network::from_weights(
+            &[LayerTopology::new(2), LayerTopology::new(3)],
+            vec![0.0; 9],
+        )
+    }
+
+    fn probes() -> Vec<Vec<f32>> {
+        vec![vec![1.0, 0.0], vec![0.5, -0.5], vec![0.2, 0.8]]
+    }
+
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            // Differences are 0.4 and -0.3
+            assert_relative_eq!(a().distance(&b()).unwrap(), 0.5);
+            assert_relative_eq!(b().distance(&a()).unwrap(), 0.5);
+            assert_relative_eq!(a().distance(&a()).unwrap(), 0.0);
+        }
+
+        #[test]
+        fn topology_mismatch() {
+            let err = a().distance(&other_topology()).err();
+
+            assert_eq!(err, Some(NetworkError::TopologyMismatch { layer: 1 }));
+        }
+
+        #[test]
+        fn kind_mismatch() {
+            let layers = [
+                LayerTopology::new(2),
+                LayerTopology::new(2),
+                LayerTopology::new(1),
+            ];
+
+            let dense: Network = Network::from_weights(&layers, vec![0.0; 9]);
+            let err = dense.distance(&a()).err();
+
+            assert_eq!(err, Some(NetworkError::TopologyMismatch { layer: 1 }));
+        }
+    }
+
+    mod functional_distance {
+        use super::*;
+
+        #[test]
+        fn same_network() {
+            assert_relative_eq!(a().functional_distance(&a(), &probes()).unwrap(), 0.0);
+        }
+
+        #[test]
+        fn different_networks() {
+            let (mut a, mut b) = (a(), b());
+            let actual = a.functional_distance(&b, &probes()).unwrap();
+
+            let expected = (probes()
+                .into_iter()
+                .map(|probe| (a.propagate(probe.clone())[0] - b.propagate(probe)[0]).powi(2))
+                .sum::<f32>()
+                / 3.0)
+                .sqrt();
+
+            assert!(actual > 0.0);
+            assert_relative_eq!(actual, expected);
+        }
+
+        #[test]
+        fn preserves_state() {
+            let (mut a, b) = (a(), b());
+
+            a.propagate(vec![1.0, 1.0]);
+            let before = a.layers[0].state.clone();
+
+            a.functional_distance(&b, &probes()).unwrap();
+
+            assert_eq!(a.layers[0].state, before);
+            assert!(b.layers[0].state.iter().all(|&state| state == 0.0));
+        }
+
+        #[test]
+        fn topology_mismatch() {
+            let err = a().functional_distance(&other_topology(), &probes()).err();
+
+            assert_eq!(err, Some(NetworkError::TopologyMismatch { layer: 1 }));
+        }
+    }
+
+    mod interpolate {
+        use super::*;
+
+        #[test]
+        fn endpoints() {
+            let (a, b) = (a(), b());
+
+            let at_a: Vec<_> = a.interpolate(&b, 0.0).unwrap().weights().collect();
+            let at_b: Vec<_> = a.interpolate(&b, 1.0).unwrap().weights().collect();
+
+            assert_relative_eq!(at_a.as_slice(), a.weights().collect::<Vec<_>>().as_slice());
+            assert_relative_eq!(at_b.as_slice(), b.weights().collect::<Vec<_>>().as_slice());
+        }
+
+        #[test]
+        fn halfway() {
+            let (a, b) = (a(), b());
+            let halfway = a.interpolate(&b, 0.5).unwrap();
+
+            assert_relative_eq!(halfway.distance(&a).unwrap(), 0.25);
+            assert_relative_eq!(halfway.distance(&b).unwrap(), 0.25);
+        }
+
+        #[test]
+        fn keeps_activations() {
+            let network = a().interpolate(&b(), 0.5).unwrap();
+
+            assert!(matches!(
+                network.topology()[2].activation,
+                Activation::Identity
+            ));
+        }
+
+        #[test]
+        fn topology_mismatch() {
+            let err = a().interpolate(&other_topology(), 0.5).err();
+
+            assert_eq!(err, Some(NetworkError::TopologyMismatch { layer: 1 }));
+        }
+    }
+
+    mod average {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let actual: Vec<_> = Network::average(&[a(), b(), b(), a()])
+                .unwrap()
+                .weights()
+                .collect();
+
+            let expected: Vec<_> = a().interpolate(&b(), 0.5).unwrap().weights().collect();
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn single_network() {
+            let actual: Vec<_> = Network::average(&[a()]).unwrap().weights().collect();
+
+            assert_relative_eq!(
+                actual.as_slice(),
+                a().weights().collect::<Vec<_>>().as_slice()
+            );
+        }
+
+        #[test]
+        fn no_networks() {
+            let err = Network::<f32>::average(&[]).err();
+
+            assert_eq!(err, Some(NetworkError::NoNetworks));
+        }
+
+        #[test]
+        fn topology_mismatch() {
+            let err = Network::average(&[a(), b(), other_topology()]).err();
+
+            assert_eq!(err, Some(NetworkError::TopologyMismatch { layer: 1 }));
+        }
+    }
+}
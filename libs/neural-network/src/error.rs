@@ -0,0 +1,81 @@
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetworkError {
+    /// A network needs at least an input and an output layer.
+    TooFewLayers {
+        actual: usize,
+    },
+    WeightCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    ZeroWidthLayer {
+        layer: usize,
+    },
+    NonFiniteWeight {
+        index: usize,
+        value: f32,
+    },
+    /// Convolution's parameters don't fit the previous layer.
+    InvalidConvolution {
+        layer: usize,
+    },
+    /// Layer's declared number of neurons doesn't match what its kind
+    /// produces (e.g. a convolution).
+    LayerSizeMismatch {
+        layer: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// Networks that were supposed to be compared or blended have
+    /// differently shaped layers, starting from given one.
+    TopologyMismatch {
+        layer: usize,
+    },
+    /// `Network::average()` got no networks to average.
+    NoNetworks,
+    /// Number of weights (of given layer, or of the network up to it)
+    /// doesn't fit in `usize`.
+    ParameterCountOverflow {
+        layer: usize,
+    },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewLayers { actual } => {
+                write!(f, "network needs at least two layers, got {}", actual)
+            }
+            Self::WeightCountMismatch { expected, actual } => {
+                write!(f, "expected {} weights, got {}", expected, actual)
+            }
+            Self::ZeroWidthLayer { layer } => write!(f, "layer #{} has no neurons", layer),
+            Self::NonFiniteWeight { index, value } => {
+                write!(f, "weight #{} is not finite: {}", index, value)
+            }
+            Self::InvalidConvolution { layer } => {
+                write!(f, "convolution at layer #{} doesn't fit its inputs", layer)
+            }
+            Self::LayerSizeMismatch {
+                layer,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "layer #{} should have {} neurons, got {}",
+                layer, expected, actual
+            ),
+            Self::TopologyMismatch { layer } => {
+                write!(f, "networks differ in shape at layer #{}", layer)
+            }
+            Self::NoNetworks => write!(f, "got no networks"),
+            Self::ParameterCountOverflow { layer } => {
+                write!(f, "layer #{} has more weights than fit in usize", layer)
+            }
+        }
+    }
+}
+
+impl core::error::Error for NetworkError {}
@@ -0,0 +1,50 @@
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{AddAssign, DivAssign, MulAssign, SubAssign},
+};
+
+use rand::distributions::uniform::SampleUniform;
+
+/// Floating-point type networks can be built upon - that is, `f32` (used
+/// by the simulation) or `f64` (handy for numerical analysis, e.g. checking
+/// gradients without fighting rounding errors).
+pub trait Float:
+    num_traits::Float
+    + SampleUniform
+    + Sum
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + Send
+    + Sync
+    + 'static
+{
+    fn from_f32(value: f32) -> Self;
+
+    fn into_f32(self) -> f32;
+}
+
+impl Float for f32 {
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    fn into_f32(self) -> f32 {
+        self
+    }
+}
+
+impl Float for f64 {
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+
+    fn into_f32(self) -> f32 {
+        self as f32
+    }
+}
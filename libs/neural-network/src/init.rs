@@ -0,0 +1,44 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InitScheme {
+    /// Draws everything from `[-1.0, 1.0]`.
+    #[default]
+    Uniform,
+
+    /// Glorot / Xavier uniform, i.e. `±sqrt(6 / (fan_in + fan_out))`;
+    /// suits tanh and sigmoid layers.
+    Xavier,
+
+    /// He uniform, i.e. `±sqrt(6 / fan_in)`; suits ReLU layers.
+    He,
+}
+
+impl InitScheme {
+    pub(crate) fn limit(self, fan_in: usize, fan_out: usize) -> f32 {
+        match self {
+            Self::Uniform => 1.0,
+            Self::Xavier => num_traits::Float::sqrt(6.0 / (fan_in + fan_out).max(1) as f32),
+            Self::He => num_traits::Float::sqrt(6.0 / fan_in.max(1) as f32),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Initialization {
+    pub scheme: InitScheme,
+    /// Starts all biases at zero instead of drawing them.
+    pub zero_bias: bool,
+}
+
+impl Initialization {
+    pub fn new(scheme: InitScheme) -> Self {
+        Self {
+            scheme,
+            zero_bias: false,
+        }
+    }
+
+    pub fn with_zero_bias(mut self) -> Self {
+        self.zero_bias = true;
+        self
+    }
+}
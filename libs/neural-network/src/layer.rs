@@ -1,36 +1,788 @@
-use crate::neuron::Neuron;
+use alloc::{vec, vec::Vec};
+use core::iter::once;
 
-pub struct Layer {
-    pub(crate) neurons: Vec<Neuron>,
+use rand::{Rng, RngCore};
+
+use crate::{
+    activation::Activation, ctrnn::Ctrnn, init::Initialization, neuron::Neuron, Float,
+    LayerTopology,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LayerKind {
+    /// Fully-connected, stateless layer.
+    #[default]
+    Dense,
+
+    /// Elman-style layer: besides its inputs, each neuron also sees the
+    /// layer's own outputs from the previous `propagate()` call.
+    Recurrent,
+
+    /// Stateless layer that slides the same set of kernels over its inputs.
+    Conv1d(Conv1d),
+
+    /// Fully-connected layer of continuous-time neurons, see `Ctrnn`.
+    Ctrnn(Ctrnn),
 }
 
-impl Layer {
-    pub fn new(neurons: Vec<Neuron>) -> Self {
-        Self { neurons }
+/// 1D convolution over an ordered sequence of cells (e.g. the eye's).
+///
+/// Inputs and outputs are laid out cell after cell, i.e. `[cell 0 channel 0,
+/// cell 0 channel 1, ..., cell 1 channel 0, ...]`, so that convolutions can
+/// be stacked on top of each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conv1d {
+    pub kernel: usize,
+    pub stride: usize,
+    /// Number of kernels, i.e. output values per position.
+    pub channels: usize,
+    /// Number of values per input cell.
+    pub in_channels: usize,
+    /// When set, the input wraps around (which suits the eye, whose cells
+    /// cover a cone) and kernels are centered on every `stride`-th cell;
+    /// otherwise only the windows that fit entirely within the input are
+    /// used.
+    pub circular: bool,
+}
+
+impl Conv1d {
+    pub fn new(kernel: usize, channels: usize) -> Self {
+        Self {
+            kernel,
+            stride: 1,
+            channels,
+            in_channels: 1,
+            circular: false,
+        }
+    }
+
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.stride = stride;
+        self
+    }
+
+    pub fn with_in_channels(mut self, in_channels: usize) -> Self {
+        self.in_channels = in_channels;
+        self
     }
 
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-        self.neurons.iter().map(|n| n.propagate(&inputs)).collect()
+    pub fn with_circular_padding(mut self) -> Self {
+        self.circular = true;
+        self
+    }
+
+    /// Returns how many numbers this convolution produces for `input_size`
+    /// inputs, or `None` if it can't be applied to them at all.
+    pub fn output_size(&self, input_size: usize) -> Option<usize> {
+        if self.kernel == 0 || self.stride == 0 || self.channels == 0 || self.in_channels == 0 {
+            return None;
+        }
+
+        if !input_size.is_multiple_of(self.in_channels) {
+            return None;
+        }
+
+        let width = input_size / self.in_channels;
+
+        let positions = if self.circular {
+            width.div_ceil(self.stride)
+        } else {
+            width.checked_sub(self.kernel)? / self.stride + 1
+        };
+
+        Some(positions * self.channels).filter(|&size| size > 0)
+    }
+
+    /// Returns indices of the inputs seen at given position, in the same
+    /// order as the kernel's weights.
+    pub(crate) fn window(
+        &self,
+        input_size: usize,
+        position: usize,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let width = (input_size / self.in_channels) as isize;
+        let mut start = (position * self.stride) as isize;
+
+        if self.circular {
+            start -= (self.kernel / 2) as isize;
+        }
+
+        (0..self.kernel).flat_map(move |offset| {
+            let cell = (start + offset as isize).rem_euclid(width) as usize;
+            (0..self.in_channels).map(move |channel| cell * self.in_channels + channel)
+        })
     }
+}
+
+/// A fully-connected or convolutional layer.
+///
+/// Weights are stored as a row-major `outputs × inputs` matrix, so that
+/// each row holds the incoming weights of one neuron; recurrent layers
+/// additionally keep an `outputs × outputs` matrix of feedback weights.
+///
+/// Convolutional layers have one row per kernel instead, shared by all the
+/// positions.
+///
+/// CTRNN layers are laid out like recurrent ones, plus they have a time
+/// constant per neuron.
+///
+/// Layers can also be sparse, in which case `mask` tells which of the input
+/// weights are actual connections - the remaining ones are kept at zero and
+/// skipped during propagation.
+#[derive(Clone)]
+pub struct Layer<F = f32> {
+    pub(crate) kind: LayerKind,
+    pub(crate) inputs: usize,
+    pub(crate) outputs: usize,
+    pub(crate) biases: Vec<F>,
+    pub(crate) weights: Vec<F>,
+    pub(crate) mask: Option<Vec<bool>>,
+    pub(crate) recurrent: Vec<F>,
+    pub(crate) taus: Vec<F>,
+    pub(crate) state: Vec<F>,
+    pub(crate) activation: Activation,
+}
 
-    pub(crate) fn random(input_neurons: usize, output_neurons: usize) -> Self {
-        let mut rng = rand::thread_rng();
+impl<F: Float> Layer<F> {
+    /// Number of genes `from_weights()` consumes for given layer; `None`
+    /// if it doesn't fit in `usize`.
+    pub fn parameter_count(input_size: usize, topology: &LayerTopology) -> Option<usize> {
+        let (rows, row_len) = shape(input_size, topology);
+        let tau = usize::from(matches!(topology.kind, LayerKind::Ctrnn(_)));
 
-        let neurons = (0..output_neurons)
-            .map(|_| Neuron::random(&mut rng, input_neurons))
-            .collect();
-        Layer { neurons }
+        row_len
+            .checked_add(1 + tau)?
+            .checked_add(recurrent_size(topology))?
+            .checked_mul(rows)
     }
 
     pub fn from_weights(
         input_size: usize,
-        output_size: usize,
-        weights: &mut dyn Iterator<Item = f32>,
+        topology: &LayerTopology,
+        weights: &mut dyn Iterator<Item = F>,
+    ) -> Self {
+        let (rows, row_len) = shape(input_size, topology);
+        let recurrent_size = recurrent_size(topology);
+
+        let mut biases = Vec::with_capacity(rows);
+        let mut matrix = Vec::with_capacity(rows * row_len);
+        let mut recurrent = Vec::with_capacity(rows * recurrent_size);
+        let mut taus = Vec::new();
+
+        for _ in 0..rows {
+            biases.push(weights.next().expect("got not enough weights"));
+
+            if let LayerKind::Ctrnn(_) = topology.kind {
+                taus.push(weights.next().expect("got not enough weights"));
+            }
+
+            for _ in 0..row_len {
+                matrix.push(weights.next().expect("got not enough weights"));
+            }
+
+            for _ in 0..recurrent_size {
+                recurrent.push(weights.next().expect("got not enough weights"));
+            }
+        }
+
+        Self::build(topology.kind, input_size, biases, matrix, recurrent, taus)
+            .with_activation(topology.activation.clone())
+    }
+
+    pub fn new(neurons: Vec<Neuron<F>>) -> Self {
+        let inputs = neurons.first().map_or(0, |n| n.weights.len());
+        let mut biases = Vec::with_capacity(neurons.len());
+        let mut weights = Vec::with_capacity(neurons.len() * inputs);
+
+        for neuron in neurons {
+            assert_eq!(neuron.weights.len(), inputs);
+
+            biases.push(neuron.bias);
+            weights.extend(neuron.weights);
+        }
+
+        Self::build(
+            LayerKind::Dense,
+            inputs,
+            biases,
+            weights,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    fn build(
+        kind: LayerKind,
+        inputs: usize,
+        biases: Vec<F>,
+        weights: Vec<F>,
+        recurrent: Vec<F>,
+        taus: Vec<F>,
+    ) -> Self {
+        let (outputs, state) = match kind {
+            LayerKind::Dense => (biases.len(), Vec::new()),
+            LayerKind::Recurrent | LayerKind::Ctrnn(_) => {
+                (biases.len(), vec![F::zero(); biases.len()])
+            }
+            LayerKind::Conv1d(conv) => (
+                conv.output_size(inputs)
+                    .expect("convolution doesn't fit its inputs"),
+                Vec::new(),
+            ),
+        };
+
+        Self {
+            kind,
+            inputs,
+            outputs,
+            biases,
+            weights,
+            mask: None,
+            recurrent,
+            taus,
+            state,
+            activation: Activation::default(),
+        }
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Disconnects inputs whose `mask` entries are `false`; `mask` follows
+    /// the layout of the input weights (recurrent weights are always kept).
+    pub fn with_mask(mut self, mask: Vec<bool>) -> Self {
+        assert_eq!(mask.len(), self.weights.len());
+
+        self.set_mask(mask);
+        self
+    }
+
+    pub(crate) fn set_mask(&mut self, mask: Vec<bool>) {
+        for (weight, &active) in self.weights.iter_mut().zip(&mask) {
+            if !active {
+                *weight = F::zero();
+            }
+        }
+
+        self.mask = Some(mask);
+    }
+
+    pub fn mask(&self) -> Option<&[bool]> {
+        self.mask.as_deref()
+    }
+
+    /// Returns the number of input weights that are actual connections.
+    pub fn connections(&self) -> usize {
+        match &self.mask {
+            Some(mask) => mask.iter().filter(|&&active| active).count(),
+            None => self.weights.len(),
+        }
+    }
+
+    /// Disconnects all inputs whose weights are closer to zero than
+    /// `threshold`; returns how many got disconnected.
+    pub fn prune(&mut self, threshold: F) -> usize {
+        let len = self.weights.len();
+        let mask = self.mask.get_or_insert_with(|| vec![true; len]);
+        let mut pruned = 0;
+
+        for (weight, active) in self.weights.iter_mut().zip(mask) {
+            if *active && weight.abs() < threshold {
+                *weight = F::zero();
+                *active = false;
+                pruned += 1;
+            }
+        }
+
+        pruned
+    }
+
+    pub fn kind(&self) -> LayerKind {
+        self.kind
+    }
+
+    pub fn activation(&self) -> &Activation {
+        &self.activation
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub fn topology(&self) -> LayerTopology {
+        LayerTopology::new(self.outputs())
+            .with_kind(self.kind)
+            .with_activation(self.activation.clone())
+    }
+
+    pub fn propagate(&mut self, inputs: Vec<F>) -> Vec<F> {
+        let mut outputs = vec![F::zero(); self.outputs()];
+        self.propagate_into(&inputs, &mut outputs);
+        outputs
+    }
+
+    pub(crate) fn propagate_into(&mut self, inputs: &[F], outputs: &mut [F]) {
+        // Taking the state out is free and lets `step()` borrow the rest
+        let mut state = core::mem::take(&mut self.state);
+        self.step(&mut state, inputs, outputs);
+        self.state = state;
+    }
+
+    /// Propagates `inputs` from given state (instead of the layer's own
+    /// one), advancing it.
+    pub(crate) fn step(&self, state: &mut [F], inputs: &[F], outputs: &mut [F]) {
+        self.forward(state, inputs, outputs);
+
+        if let LayerKind::Ctrnn(_) = self.kind {
+            for ((state, pre), bias) in state.iter_mut().zip(outputs.iter()).zip(&self.biases) {
+                *state = *pre - *bias;
+            }
+        }
+
+        for output in outputs.iter_mut() {
+            *output = self.activation.apply(*output);
+        }
+
+        if self.kind == LayerKind::Recurrent {
+            state.copy_from_slice(outputs);
+        }
+    }
+
+    /// Computes pre-activation values from given state, leaving it intact.
+    pub(crate) fn forward(&self, state: &[F], inputs: &[F], pre: &mut [F]) {
+        assert_eq!(inputs.len(), self.inputs);
+        assert_eq!(pre.len(), self.outputs());
+
+        if let LayerKind::Conv1d(conv) = &self.kind {
+            for (position, pre) in pre.chunks_exact_mut(conv.channels).enumerate() {
+                for (row, pre) in pre.iter_mut().enumerate() {
+                    *pre = conv
+                        .window(self.inputs, position)
+                        .zip(self.row(row))
+                        .zip(self.row_mask(row))
+                        .filter(|(_, active)| *active)
+                        .map(|((input, &weight), _)| inputs[input] * weight)
+                        .sum::<F>()
+                        + self.biases[row];
+                }
+            }
+
+            return;
+        }
+
+        if let LayerKind::Ctrnn(ctrnn) = &self.kind {
+            for (row, pre) in pre.iter_mut().enumerate() {
+                let net = self.input_dot(row, inputs) + self.fired_dot(row, state);
+                let rate = ctrnn.rate(self.taus[row]);
+
+                *pre = state[row] + rate * (net - state[row]) + self.biases[row];
+            }
+
+            return;
+        }
+
+        for (row, pre) in pre.iter_mut().enumerate() {
+            *pre = self.input_dot(row, inputs)
+                + dot(self.recurrent_row(row), state)
+                + self.biases[row];
+        }
+    }
+
+    /// Returns the dot product of given neuron's recurrent weights and
+    /// outputs of the CTRNN layer's neurons; they're computed on the fly,
+    /// so that propagation doesn't allocate.
+    fn fired_dot(&self, row: usize, state: &[F]) -> F {
+        self.recurrent_row(row)
+            .iter()
+            .zip(self.fired(state))
+            .map(|(&weight, fired)| weight * fired)
+            .sum()
+    }
+
+    /// Returns outputs of a CTRNN layer's neurons for given potentials.
+    fn fired<'a>(&'a self, state: &'a [F]) -> impl Iterator<Item = F> + 'a {
+        state
+            .iter()
+            .zip(&self.biases)
+            .map(|(&state, &bias)| self.activation.apply(state + bias))
+    }
+
+    fn input_dot(&self, row: usize, inputs: &[F]) -> F {
+        match self.mask {
+            None => dot(self.row(row), inputs),
+            Some(_) => self
+                .row(row)
+                .iter()
+                .zip(inputs)
+                .zip(self.row_mask(row))
+                .filter(|(_, active)| *active)
+                .map(|((&weight, &input), _)| weight * input)
+                .sum(),
+        }
+    }
+
+    /// Backpropagates `grad_outputs` (i.e. dLoss / dOutputs) through this
+    /// layer: parameter gradients get accumulated into `grad` (laid out the
+    /// same way as `weights()`), and dLoss / dInputs is written into
+    /// `grad_inputs`.
+    ///
+    /// The recurrent state is treated as a constant input, i.e. gradients
+    /// don't flow back in time.
+    pub(crate) fn backward(
+        &self,
+        inputs: &[F],
+        pre: &[F],
+        outputs: &[F],
+        grad_outputs: &[F],
+        grad: &mut [F],
+        grad_inputs: &mut [F],
+    ) {
+        grad_inputs.fill(F::zero());
+
+        if let LayerKind::Conv1d(conv) = &self.kind {
+            let stride = 1 + self.row_len();
+
+            for (idx, &grad_output) in grad_outputs.iter().enumerate() {
+                let (position, row) = (idx / conv.channels, idx % conv.channels);
+                let delta = grad_output * self.activation.derivative(pre[idx], outputs[idx]);
+                let (grad_bias, grad_weights) =
+                    grad[row * stride..][..stride].split_first_mut().unwrap();
+
+                *grad_bias += delta;
+
+                for (((input, grad), weight), active) in conv
+                    .window(self.inputs, position)
+                    .zip(grad_weights)
+                    .zip(self.row(row))
+                    .zip(self.row_mask(row))
+                {
+                    if active {
+                        *grad += delta * inputs[input];
+                    }
+
+                    grad_inputs[input] += delta * *weight;
+                }
+            }
+
+            return;
+        }
+
+        if let LayerKind::Ctrnn(ctrnn) = &self.kind {
+            self.backward_ctrnn(ctrnn, inputs, pre, outputs, grad_outputs, grad, grad_inputs);
+            return;
+        }
+
+        let state = &self.state;
+        let stride = grad.len() / self.outputs();
+
+        for (row, grad) in grad.chunks_exact_mut(stride).enumerate() {
+            let delta = grad_outputs[row] * self.activation.derivative(pre[row], outputs[row]);
+
+            let (grad_bias, grad) = grad.split_first_mut().unwrap();
+            let (grad_weights, grad_recurrent) = grad.split_at_mut(self.inputs);
+
+            *grad_bias += delta;
+
+            for ((grad, input), active) in
+                grad_weights.iter_mut().zip(inputs).zip(self.row_mask(row))
+            {
+                if active {
+                    *grad += delta * *input;
+                }
+            }
+
+            for (grad, state) in grad_recurrent.iter_mut().zip(state.iter()) {
+                *grad += delta * *state;
+            }
+
+            for (grad, weight) in grad_inputs.iter_mut().zip(self.row(row)) {
+                *grad += delta * *weight;
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward_ctrnn(
+        &self,
+        ctrnn: &Ctrnn,
+        inputs: &[F],
+        pre: &[F],
+        outputs: &[F],
+        grad_outputs: &[F],
+        grad: &mut [F],
+        grad_inputs: &mut [F],
+    ) {
+        let state = &self.state;
+        let stride = grad.len() / self.outputs();
+
+        for row in 0..self.outputs() {
+            let delta = grad_outputs[row] * self.activation.derivative(pre[row], outputs[row]);
+            let tau = self.taus[row];
+            let rate = ctrnn.rate(tau);
+            let net = self.input_dot(row, inputs) + self.fired_dot(row, state);
+
+            grad[row * stride] += delta;
+            grad[row * stride + 1] += delta * (net - state[row]) * ctrnn.rate_derivative(tau);
+
+            let grad_weights = &mut grad[row * stride + 2..][..self.inputs];
+
+            for ((grad, input), active) in
+                grad_weights.iter_mut().zip(inputs).zip(self.row_mask(row))
+            {
+                if active {
+                    *grad += delta * rate * *input;
+                }
+            }
+
+            for (grad, weight) in grad_inputs.iter_mut().zip(self.row(row)) {
+                *grad += delta * rate * *weight;
+            }
+
+            // Recurrent weights, and - since other neurons' outputs depend
+            // on their biases - these biases too
+            for ((from, &weight), fired) in self
+                .recurrent_row(row)
+                .iter()
+                .enumerate()
+                .zip(self.fired(state))
+            {
+                let x = state[from] + self.biases[from];
+                let slope = self.activation.derivative(x, fired);
+
+                grad[row * stride + 2 + self.inputs + from] += delta * rate * fired;
+                grad[from * stride] += delta * rate * weight * slope;
+            }
+        }
+    }
+
+    /// Visits all parameters in the `weights()` order.
+    pub(crate) fn for_each_weight_mut(&mut self, f: impl FnMut(&mut F)) {
+        self.visit_weights_mut(false, f);
+    }
+
+    /// Visits parameters in the `active_weights()` order.
+    pub(crate) fn for_each_active_weight_mut(&mut self, f: impl FnMut(&mut F)) {
+        self.visit_weights_mut(true, f);
+    }
+
+    fn visit_weights_mut(&mut self, active_only: bool, mut f: impl FnMut(&mut F)) {
+        let (row_len, outputs) = (self.row_len(), self.outputs());
+
+        for row in 0..self.biases.len() {
+            f(&mut self.biases[row]);
+
+            if let Some(tau) = self.taus.get_mut(row) {
+                f(tau);
+            }
+
+            let weights = self.weights[row * row_len..][..row_len].iter_mut();
+
+            match &self.mask {
+                Some(mask) if active_only => weights
+                    .zip(&mask[row * row_len..][..row_len])
+                    .filter(|(_, &active)| active)
+                    .for_each(|(weight, _)| f(weight)),
+
+                _ => weights.for_each(&mut f),
+            }
+
+            if !self.recurrent.is_empty() {
+                self.recurrent[row * outputs..][..outputs]
+                    .iter_mut()
+                    .for_each(&mut f);
+            }
+        }
+    }
+
+    /// Forgets whatever a recurrent layer has seen so far; no-op for
+    /// stateless layers.
+    pub fn reset_state(&mut self) {
+        self.state.fill(F::zero());
+    }
+
+    pub(crate) fn random(
+        rng: &mut dyn RngCore,
+        input_neurons: usize,
+        topology: &LayerTopology,
+        init: Initialization,
     ) -> Self {
-        let neurons = (0..output_size)
-            .map(|_| Neuron::from_weights(input_size, weights))
-            .collect();
+        let (rows, row_len) = shape(input_neurons, topology);
+        let fan_in = row_len + recurrent_size(topology);
+        let fan_out = match topology.kind {
+            LayerKind::Conv1d(conv) => conv.channels * conv.kernel,
+            _ => topology.neurons,
+        };
+
+        let limit = F::from_f32(init.scheme.limit(fan_in, fan_out));
+        let mut genes = Vec::with_capacity(
+            Self::parameter_count(input_neurons, topology).expect("layer has too many weights"),
+        );
+
+        for _ in 0..rows {
+            genes.push(if init.zero_bias {
+                F::zero()
+            } else {
+                rng.gen_range(-limit..=limit)
+            });
+
+            if let LayerKind::Ctrnn(ctrnn) = topology.kind {
+                genes.push(F::from_f32(ctrnn.dt) * rng.gen_range(F::one()..=F::from_f32(10.0)));
+            }
+
+            genes.extend((0..fan_in).map(|_| rng.gen_range(-limit..=limit)));
+        }
+
+        Self::from_weights(input_neurons, topology, &mut genes.into_iter())
+    }
+
+    /// Returns this layer's parameters in the chromosome order, that is:
+    /// for each neuron, its bias followed by its input weights and then
+    /// its recurrent weights (if any); CTRNN neurons have their time
+    /// constant right after the bias.
+    ///
+    /// For convolutional layers that's each kernel's bias followed by its
+    /// weights, listed just once no matter how many positions they cover.
+    ///
+    /// Disconnected inputs are included (as zeros), so that the layout
+    /// depends only on the topology.
+    pub fn weights(&self) -> impl Iterator<Item = F> + '_ {
+        (0..self.biases.len())
+            .flat_map(|row| {
+                once(&self.biases[row])
+                    .chain(self.taus.get(row))
+                    .chain(self.row(row))
+                    .chain(self.recurrent_row(row))
+            })
+            .copied()
+    }
+
+    /// Like `weights()`, but skips disconnected inputs.
+    pub fn active_weights(&self) -> impl Iterator<Item = F> + '_ {
+        (0..self.biases.len())
+            .flat_map(|row| {
+                let inputs = self
+                    .row(row)
+                    .iter()
+                    .zip(self.row_mask(row))
+                    .filter(|(_, active)| *active)
+                    .map(|(weight, _)| weight);
+
+                once(&self.biases[row])
+                    .chain(self.taus.get(row))
+                    .chain(inputs)
+                    .chain(self.recurrent_row(row))
+            })
+            .copied()
+    }
+
+    /// Returns the bias of given output.
+    pub(crate) fn bias(&self, output: usize) -> F {
+        match self.kind {
+            LayerKind::Conv1d(conv) => self.biases[output % conv.channels],
+            LayerKind::Dense | LayerKind::Recurrent | LayerKind::Ctrnn(_) => self.biases[output],
+        }
+    }
+
+    /// Returns all active input connections as `(input, output, weight)`;
+    /// convolutions yield one edge per position a kernel weight is used at.
+    pub(crate) fn edges(&self) -> Vec<(usize, usize, F)> {
+        let mut edges = Vec::new();
+
+        match self.kind {
+            LayerKind::Conv1d(conv) => {
+                for output in 0..self.outputs {
+                    let (position, row) = (output / conv.channels, output % conv.channels);
+
+                    edges.extend(
+                        conv.window(self.inputs, position)
+                            .zip(self.row(row))
+                            .zip(self.row_mask(row))
+                            .filter(|(_, active)| *active)
+                            .map(|((input, &weight), _)| (input, output, weight)),
+                    );
+                }
+            }
 
-        Self { neurons }
+            LayerKind::Dense | LayerKind::Recurrent | LayerKind::Ctrnn(_) => {
+                for output in 0..self.outputs {
+                    edges.extend(
+                        self.row(output)
+                            .iter()
+                            .zip(self.row_mask(output))
+                            .enumerate()
+                            .filter(|(_, (_, active))| *active)
+                            .map(|(input, (&weight, _))| (input, output, weight)),
+                    );
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Returns feedback connections as `(from, to, weight)`.
+    pub(crate) fn recurrent_edges(&self) -> impl Iterator<Item = (usize, usize, F)> + '_ {
+        (0..self.biases.len()).flat_map(move |to| {
+            self.recurrent_row(to)
+                .iter()
+                .enumerate()
+                .map(move |(from, &weight)| (from, to, weight))
+        })
     }
-}
\ No newline at end of file
+
+    fn row_len(&self) -> usize {
+        match self.kind {
+            LayerKind::Dense | LayerKind::Recurrent | LayerKind::Ctrnn(_) => self.inputs,
+            LayerKind::Conv1d(conv) => conv.kernel * conv.in_channels,
+        }
+    }
+
+    fn row(&self, row: usize) -> &[F] {
+        let len = self.row_len();
+        &self.weights[row * len..][..len]
+    }
+
+    fn row_mask(&self, row: usize) -> impl Iterator<Item = bool> + '_ {
+        let len = self.row_len();
+        let mask = self.mask.as_ref().map(|mask| &mask[row * len..][..len]);
+
+        (0..len).map(move |idx| mask.is_none_or(|mask| mask[idx]))
+    }
+
+    fn recurrent_row(&self, row: usize) -> &[F] {
+        match self.kind {
+            LayerKind::Dense | LayerKind::Conv1d(_) => &[],
+            LayerKind::Recurrent | LayerKind::Ctrnn(_) => {
+                &self.recurrent[row * self.outputs()..][..self.outputs()]
+            }
+        }
+    }
+}
+
+/// Returns the number of rows (neurons or kernels) and the number of
+/// input weights per row.
+fn shape(input_size: usize, topology: &LayerTopology) -> (usize, usize) {
+    match topology.kind {
+        LayerKind::Dense | LayerKind::Recurrent | LayerKind::Ctrnn(_) => {
+            (topology.neurons, input_size)
+        }
+        LayerKind::Conv1d(conv) => (conv.channels, conv.kernel * conv.in_channels),
+    }
+}
+
+fn recurrent_size(topology: &LayerTopology) -> usize {
+    match topology.kind {
+        LayerKind::Dense | LayerKind::Conv1d(_) => 0,
+        LayerKind::Recurrent | LayerKind::Ctrnn(_) => topology.neurons,
+    }
+}
+
+fn dot<F: Float>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).map(|(&a, &b)| a * b).sum()
+}
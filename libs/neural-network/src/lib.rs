@@ -1,63 +1,387 @@
-use std::iter::once;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
 
 use layer::Layer;
 
+use rand::RngCore;
+
+pub use self::{
+    activation::Activation,
+    buffer::PropagationBuffer,
+    ctrnn::Ctrnn,
+    error::NetworkError,
+    float::Float,
+    init::{InitScheme, Initialization},
+    layer::{Conv1d, LayerKind},
+    quantized::QuantizedNetwork,
+    report::{LayerSummary, Summary, WeightStats},
+};
+
+pub mod activation;
+pub mod buffer;
+mod ctrnn;
+mod distance;
+mod error;
+mod float;
+mod init;
 pub mod layer;
+pub mod neat;
 pub mod neuron;
+#[cfg(feature = "serde")]
+pub mod persist;
+pub mod quantized;
+mod report;
+pub mod train;
 
-pub struct Network {
-    layers: Vec<Layer>,
+#[derive(Clone)]
+pub struct Network<F = f32> {
+    layers: Vec<Layer<F>>,
 }
 
-impl Network {
-    pub fn new(layers: Vec<Layer>) -> Self {
-        assert!(layers.len() > 1);
-
-        Self { layers }
+impl<F: Float> Network<F> {
+    pub fn new(layers: Vec<Layer<F>>) -> Self {
+        Self::try_new(layers).unwrap_or_else(|err| panic!("{}", err))
     }
 
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-        self.layers
-            .iter()
-            .fold(inputs, |inputs, layer| layer.propagate(inputs))
+    pub fn try_new(layers: Vec<Layer<F>>) -> Result<Self, NetworkError> {
+        if layers.len() < 2 {
+            return Err(NetworkError::TooFewLayers {
+                actual: layers.len(),
+            });
+        }
+
+        if layers[0].inputs() == 0 {
+            return Err(NetworkError::ZeroWidthLayer { layer: 0 });
+        }
+
+        if let Some(layer) = layers.iter().position(|layer| layer.outputs() == 0) {
+            return Err(NetworkError::ZeroWidthLayer { layer: layer + 1 });
+        }
+
+        for (layer, pair) in layers.windows(2).enumerate() {
+            if pair[0].outputs() != pair[1].inputs() {
+                return Err(NetworkError::LayerSizeMismatch {
+                    layer: layer + 1,
+                    expected: pair[1].inputs(),
+                    actual: pair[0].outputs(),
+                });
+            }
+        }
+
+        let this = Self { layers };
+        check_finite(this.weights())?;
+
+        Ok(this)
     }
 
-    pub fn random(layers: &[LayerTopology]) -> Self {
+    pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology], init: Initialization) -> Self {
         let layers = layers
             .windows(2)
-            .map(|layer| Layer::random(layer[0].neurons, layer[1].neurons))
+            .map(|layer| Layer::<F>::random(rng, layer[0].neurons, &layer[1], init))
             .collect();
         Network { layers }
     }
 
-    pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
-        self.layers
-            .iter()
-            .flat_map(|layer| layer.neurons.iter())
-            .flat_map(|neuron| once(&neuron.bias).chain(&neuron.weights))
-            .copied()
+    pub fn from_weights(layers: &[LayerTopology], weights: impl IntoIterator<Item = F>) -> Self {
+        Self::try_from_weights(layers, weights).unwrap_or_else(|err| panic!("{}", err))
     }
 
-    pub fn from_weights(layers: &[LayerTopology], weights: impl IntoIterator<Item = f32>) -> Self {
-        assert!(layers.len() > 1);
+    pub fn try_from_weights(
+        layers: &[LayerTopology],
+        weights: impl IntoIterator<Item = F>,
+    ) -> Result<Self, NetworkError> {
+        let expected = Self::parameter_count(layers)?;
+        let weights: Vec<_> = weights.into_iter().collect();
+
+        if weights.len() != expected {
+            return Err(NetworkError::WeightCountMismatch {
+                expected,
+                actual: weights.len(),
+            });
+        }
+
+        check_finite(weights.iter().copied())?;
 
         let mut weights = weights.into_iter();
 
         let layers = layers
             .windows(2)
-            .map(|layers| Layer::from_weights(layers[0].neurons, layers[1].neurons, &mut weights))
+            .map(|layers| Layer::from_weights(layers[0].neurons, &layers[1], &mut weights))
             .collect();
 
-        if weights.next().is_some() {
-            panic!("got too many weights");
+        Ok(Self { layers })
+    }
+
+    /// Returns how many weights `from_weights()` expects for given topology.
+    pub fn parameter_count(layers: &[LayerTopology]) -> Result<usize, NetworkError> {
+        if layers.len() < 2 {
+            return Err(NetworkError::TooFewLayers {
+                actual: layers.len(),
+            });
+        }
+
+        if let Some(layer) = layers.iter().position(|layer| layer.neurons == 0) {
+            return Err(NetworkError::ZeroWidthLayer { layer });
+        }
+
+        for (layer, pair) in layers.windows(2).enumerate() {
+            if let LayerKind::Conv1d(conv) = pair[1].kind {
+                let layer = layer + 1;
+
+                let expected = conv
+                    .output_size(pair[0].neurons)
+                    .ok_or(NetworkError::InvalidConvolution { layer })?;
+
+                if expected != pair[1].neurons {
+                    return Err(NetworkError::LayerSizeMismatch {
+                        layer,
+                        expected,
+                        actual: pair[1].neurons,
+                    });
+                }
+            }
+        }
+
+        layers
+            .windows(2)
+            .enumerate()
+            .try_fold(0usize, |count, (layer, pair)| {
+                Layer::<F>::parameter_count(pair[0].neurons, &pair[1])
+                    .and_then(|layer_count| count.checked_add(layer_count))
+                    .ok_or(NetworkError::ParameterCountOverflow { layer: layer + 1 })
+            })
+    }
+
+    pub fn propagate(&mut self, inputs: Vec<F>) -> Vec<F> {
+        self.layers
+            .iter_mut()
+            .fold(inputs, |inputs, layer| layer.propagate(inputs))
+    }
+
+    /// Like `propagate()`, but returns the activations of every layer:
+    /// the first item is `inputs`, then come hidden layers, and the last
+    /// item is what `propagate()` would have returned.
+    pub fn propagate_traced(&mut self, inputs: Vec<F>) -> Vec<Vec<F>> {
+        let mut trace = Vec::with_capacity(self.layers.len() + 1);
+
+        let outputs = self.layers.iter_mut().fold(inputs, |inputs, layer| {
+            let mut outputs = vec![F::zero(); layer.outputs()];
+            layer.propagate_into(&inputs, &mut outputs);
+            trace.push(inputs);
+            outputs
+        });
+
+        trace.push(outputs);
+        trace
+    }
+
+    /// Propagates `samples` as a sequence, like consecutive `propagate_traced()`
+    /// calls would, but through a copy of this network - so its recurrent
+    /// state stays intact.
+    pub(crate) fn propagate_detached<'a>(
+        &self,
+        samples: &'a [Vec<F>],
+    ) -> impl Iterator<Item = Vec<Vec<F>>> + 'a
+    where
+        F: 'a,
+    {
+        let mut network = self.clone();
+
+        samples
+            .iter()
+            .map(move |sample| network.propagate_traced(sample.clone()))
+    }
+
+    /// Allocation-free counterpart of `propagate()`.
+    ///
+    /// `out` must be exactly `output_size()` long.
+    pub fn propagate_into(
+        &mut self,
+        inputs: &[F],
+        scratch: &mut PropagationBuffer<F>,
+        out: &mut [F],
+    ) {
+        assert_eq!(inputs.len(), self.input_size());
+        scratch.fit(self.widest_layer());
+
+        let (last, hidden) = self.layers.split_last_mut().unwrap();
+
+        scratch.front[..inputs.len()].copy_from_slice(inputs);
+
+        for layer in hidden {
+            layer.propagate_into(
+                &scratch.front[..layer.inputs()],
+                &mut scratch.back[..layer.outputs()],
+            );
+
+            core::mem::swap(&mut scratch.front, &mut scratch.back);
+        }
+
+        last.propagate_into(&scratch.front[..last.inputs()], out);
+    }
+
+    /// Propagates many input vectors at once.
+    ///
+    /// `inputs` is a row-major matrix with one row of `input_size()`
+    /// numbers per sample; the result is laid out the same way, with
+    /// `output_size()` numbers per sample.
+    ///
+    /// Samples are independent of each other: recurrent layers propagate
+    /// each one from their current state, which is left intact.
+    pub fn propagate_batch(&self, inputs: &[F]) -> Vec<F> {
+        let input_size = self.input_size();
+        assert!(input_size > 0 && inputs.len().is_multiple_of(input_size));
+
+        let samples = inputs.len() / input_size;
+        let mut inputs = inputs.to_vec();
+        let mut outputs = Vec::new();
+        let mut state = Vec::new();
+
+        for layer in &self.layers {
+            outputs.clear();
+            outputs.resize(samples * layer.outputs(), F::zero());
+
+            for (input, output) in inputs
+                .chunks_exact(layer.inputs())
+                .zip(outputs.chunks_exact_mut(layer.outputs()))
+            {
+                state.clone_from(&layer.state);
+                layer.step(&mut state, input, output);
+            }
+
+            core::mem::swap(&mut inputs, &mut outputs);
         }
 
-        Self { layers }
+        inputs
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.layers[0].inputs()
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.layers[self.layers.len() - 1].outputs()
+    }
+
+    /// Returns the topology this network could be re-created from, input
+    /// layer included.
+    pub fn topology(&self) -> Vec<LayerTopology> {
+        core::iter::once(LayerTopology::new(self.input_size()))
+            .chain(self.layers.iter().map(Layer::topology))
+            .collect()
+    }
+
+    pub(crate) fn widest_layer(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|layer| layer.inputs().max(layer.outputs()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Clears the memory of all recurrent layers, e.g. before the network
+    /// is shown an unrelated sequence of inputs.
+    pub fn reset_state(&mut self) {
+        for layer in &mut self.layers {
+            layer.reset_state();
+        }
+    }
+
+    pub fn weights(&self) -> impl Iterator<Item = F> + '_ {
+        self.layers.iter().flat_map(Layer::weights)
+    }
+
+    /// Disconnects all inputs whose weights are closer to zero than
+    /// `threshold`; returns how many got disconnected.
+    pub fn prune(&mut self, threshold: F) -> usize {
+        self.layers
+            .iter_mut()
+            .map(|layer| layer.prune(threshold))
+            .sum()
+    }
+
+    /// Returns the number of (input) connections still in use.
+    pub fn connections(&self) -> usize {
+        self.layers.iter().map(Layer::connections).sum()
     }
+
+    /// Like `weights()`, but skips connections removed by `prune()`.
+    ///
+    /// Since the result depends on the mask, it can be only loaded back into
+    /// a network of the same shape, through `set_active_weights()`.
+    pub fn active_weights(&self) -> impl Iterator<Item = F> + '_ {
+        self.layers.iter().flat_map(Layer::active_weights)
+    }
+
+    pub fn set_active_weights(
+        &mut self,
+        weights: impl IntoIterator<Item = F>,
+    ) -> Result<(), NetworkError> {
+        let expected = self.active_weights().count();
+        let weights: Vec<_> = weights.into_iter().collect();
+
+        if weights.len() != expected {
+            return Err(NetworkError::WeightCountMismatch {
+                expected,
+                actual: weights.len(),
+            });
+        }
+
+        check_finite(weights.iter().copied())?;
+
+        let mut weights = weights.into_iter();
+
+        for layer in &mut self.layers {
+            layer.for_each_active_weight_mut(|weight| *weight = weights.next().unwrap());
+        }
+
+        Ok(())
+    }
+}
+
+fn check_finite<F: Float>(weights: impl Iterator<Item = F>) -> Result<(), NetworkError> {
+    for (index, value) in weights.enumerate() {
+        if !value.is_finite() {
+            return Err(NetworkError::NonFiniteWeight {
+                index,
+                value: value.into_f32(),
+            });
+        }
+    }
+
+    Ok(())
 }
 
+#[derive(Clone, Debug)]
 pub struct LayerTopology {
     pub neurons: usize,
+    /// Applied to this layer's outputs; ignored for the input layer.
+    pub activation: Activation,
+    /// Ignored for the input layer.
+    pub kind: LayerKind,
+}
+
+impl LayerTopology {
+    pub fn new(neurons: usize) -> Self {
+        Self {
+            neurons,
+            activation: Activation::default(),
+            kind: LayerKind::default(),
+        }
+    }
+
+    pub fn with_kind(mut self, kind: LayerKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +392,14 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn is_send_and_sync() {
+        fn check<T: Send + Sync>() {}
+
+        check::<Network>();
+        check::<QuantizedNetwork>();
+    }
+
     mod random {
         use crate::neuron::Neuron;
 
@@ -76,7 +408,7 @@ mod tests {
         #[test]
         fn test() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
-            let neuron = Neuron::random(&mut rng, 4);
+            let neuron: Neuron = Neuron::random(&mut rng, 4);
 
             assert_relative_eq!(neuron.bias, -0.6255188);
 
@@ -87,9 +419,75 @@ mod tests {
         }
     }
 
-    mod propagate {
+    mod network_random {
         use crate::neuron::Neuron;
 
+        use super::*;
+
+        fn layers() -> [LayerTopology; 3] {
+            [
+                LayerTopology::new(4),
+                LayerTopology::new(3).with_kind(LayerKind::Recurrent),
+                LayerTopology::new(2),
+            ]
+        }
+
+        fn weights(init: Initialization) -> Vec<f32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            Network::random(&mut rng, &layers(), init)
+                .weights()
+                .collect()
+        }
+
+        #[test]
+        fn is_reproducible() {
+            let init = Initialization::new(InitScheme::Xavier);
+
+            assert_eq!(weights(init), weights(init));
+        }
+
+        #[test]
+        fn uniform_matches_neuron_random() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let neuron: Neuron = Neuron::random(&mut rng, 4);
+
+            let actual = weights(Initialization::default());
+
+            approx::assert_relative_eq!(actual[0], neuron.bias);
+            approx::assert_relative_eq!(&actual[1..5], neuron.weights.as_slice());
+        }
+
+        #[test]
+        fn he() {
+            // Fan-in of the recurrent layer is 4 inputs + 3 recurrent
+            let limit = (6.0f32 / 7.0).sqrt();
+
+            let actual = weights(Initialization::new(InitScheme::He));
+
+            assert!(actual[..24].iter().all(|w| w.abs() <= limit));
+            assert!(actual[..24].iter().any(|w| w.abs() > 0.5 * limit));
+        }
+
+        #[test]
+        fn zero_bias() {
+            let actual = weights(Initialization::new(InitScheme::Xavier).with_zero_bias());
+
+            // Biases come first in each neuron's genes: 3 neurons with 1 + 4
+            // + 3 genes each, then 2 neurons with 1 + 3 genes each
+            for bias in [0, 8, 16, 24, 28] {
+                assert_eq!(actual[bias], 0.0);
+            }
+
+            assert!(actual[1..8].iter().all(|w| *w != 0.0));
+        }
+    }
+
+    mod propagate {
+        use crate::{neuron::Neuron, Activation};
+
+        use super::*;
+
         #[test]
         fn test() {
             let neuron = Neuron {
@@ -98,14 +496,606 @@ mod tests {
             };
 
             // Ensures `.max()` (our ReLU) works:
-            approx::assert_relative_eq!(neuron.propagate(&[-10.0, -10.0]), 0.0,);
+            approx::assert_relative_eq!(neuron.propagate(&[-10.0, -10.0], &Activation::ReLU), 0.0,);
 
             // `0.5` and `1.0` chosen by a fair dice roll:
             approx::assert_relative_eq!(
-                neuron.propagate(&[0.5, 1.0]),
+                neuron.propagate(&[0.5, 1.0], &Activation::ReLU),
                 (-0.3 * 0.5) + (0.8 * 1.0) + 0.5,
             );
         }
+
+        #[test]
+        fn honors_layer_activations() {
+            let mut network: Network = Network::new(vec![
+                Layer::new(vec![Neuron::new(0.0, vec![1.0, -1.0])]),
+                Layer::new(vec![Neuron::new(-0.5, vec![1.0])]).with_activation(Activation::Tanh),
+            ]);
+
+            // The hidden ReLU clamps `-1.0` to zero, so only the output
+            // layer's bias reaches tanh - and it stays negative:
+            approx::assert_relative_eq!(
+                network.propagate(vec![1.0, 2.0]).as_slice(),
+                [(-0.5f32).tanh()].as_ref(),
+            );
+        }
+    }
+
+    mod propagate_traced {
+        use crate::neuron::Neuron;
+
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut network: Network = Network::new(vec![
+                Layer::new(vec![
+                    Neuron::new(0.0, vec![1.0, 0.0]),
+                    Neuron::new(0.0, vec![0.0, -1.0]),
+                ]),
+                Layer::new(vec![Neuron::new(0.5, vec![1.0, 1.0])]),
+            ]);
+
+            let trace = network.propagate_traced(vec![2.0, 3.0]);
+
+            assert_eq!(trace, vec![vec![2.0, 3.0], vec![2.0, 0.0], vec![2.5]]);
+            assert_eq!(trace.last().unwrap(), &network.propagate(vec![2.0, 3.0]));
+        }
+    }
+
+    mod propagate_batch {
+        use crate::neuron::Neuron;
+
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut network: Network = Network::new(vec![
+                Layer::new(vec![
+                    Neuron::new(0.1, vec![0.2, -0.3]),
+                    Neuron::new(-0.4, vec![0.5, 0.6]),
+                ]),
+                Layer::new(vec![Neuron::new(0.7, vec![-0.8, 0.9])])
+                    .with_activation(Activation::Identity),
+            ]);
+
+            let samples = [[1.0, 2.0], [-3.0, 0.5], [0.0, 0.0]];
+
+            let expected: Vec<_> = samples
+                .iter()
+                .flat_map(|sample| network.propagate(sample.to_vec()))
+                .collect();
+
+            let actual = network.propagate_batch(&samples.concat());
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+
+    mod propagate_into {
+        use crate::neuron::Neuron;
+
+        use super::*;
+
+        fn network(bias: f32) -> Network {
+            Network::new(vec![
+                Layer::new(vec![
+                    Neuron::new(bias, vec![0.2, -0.3]),
+                    Neuron::new(-0.4, vec![0.5, 0.6]),
+                    Neuron::new(0.3, vec![-0.1, 0.4]),
+                ]),
+                Layer::new(vec![Neuron::new(0.7, vec![-0.8, 0.9, 0.1])])
+                    .with_activation(Activation::Identity),
+            ])
+        }
+
+        #[test]
+        fn matches_propagate() {
+            let mut network = network(0.1);
+            let mut scratch = PropagationBuffer::new(&network);
+            let mut out = [0.0];
+
+            network.propagate_into(&[1.0, 2.0], &mut scratch, &mut out);
+
+            approx::assert_relative_eq!(out.as_ref(), network.propagate(vec![1.0, 2.0]).as_slice());
+        }
+
+        #[test]
+        fn reuses_buffer_across_networks_of_the_same_shape() {
+            let (mut a, mut b) = (network(0.1), network(-0.9));
+            let mut scratch = PropagationBuffer::new(&a);
+            let mut out = [0.0];
+
+            a.propagate_into(&[1.0, 2.0], &mut scratch, &mut out);
+            b.propagate_into(&[1.0, 2.0], &mut scratch, &mut out);
+
+            approx::assert_relative_eq!(out.as_ref(), b.propagate(vec![1.0, 2.0]).as_slice());
+        }
+    }
+
+    mod recurrent {
+        use super::*;
+
+        fn network() -> Network {
+            let layers = &[
+                LayerTopology::new(1),
+                LayerTopology::new(1)
+                    .with_kind(LayerKind::Recurrent)
+                    .with_activation(Activation::Identity),
+            ];
+
+            // bias, input weight, recurrent weight
+            Network::from_weights(layers, vec![0.0, 1.0, 0.5])
+        }
+
+        #[test]
+        fn remembers_previous_outputs() {
+            let mut network = network();
+
+            let actual: Vec<_> = [1.0, 0.0, 0.0]
+                .into_iter()
+                .flat_map(|input| network.propagate(vec![input]))
+                .collect();
+
+            approx::assert_relative_eq!(actual.as_slice(), [1.0, 0.5, 0.25].as_ref());
+        }
+
+        #[test]
+        fn propagate_batch_isolates_samples() {
+            let mut network = network();
+            network.propagate(vec![1.0]);
+
+            // Each sample starts from the same state, so each sees 0.5
+            // coming back from the previous step
+            approx::assert_relative_eq!(
+                network.propagate_batch(&[0.0, 0.0, 2.0]).as_slice(),
+                [0.5, 0.5, 2.5].as_ref()
+            );
+
+            // ... and the state itself stays as it was
+            approx::assert_relative_eq!(network.propagate(vec![0.0]).as_slice(), [0.5].as_ref());
+        }
+
+        #[test]
+        fn reset_state() {
+            let mut network = network();
+
+            network.propagate(vec![1.0]);
+            network.reset_state();
+
+            approx::assert_relative_eq!(network.propagate(vec![0.0]).as_slice(), [0.0].as_ref());
+        }
+
+        #[test]
+        fn weights() {
+            let layers = &[
+                LayerTopology::new(2),
+                LayerTopology::new(2).with_kind(LayerKind::Recurrent),
+                LayerTopology::new(1),
+            ];
+
+            // 2 * (1 + 2 + 2) recurrent + (1 + 2) dense
+            let weights: Vec<_> = (0..13).map(|n| n as f32).collect();
+
+            let network: Network = Network::from_weights(layers, weights.clone());
+            let actual: Vec<_> = network.weights().collect();
+
+            approx::assert_relative_eq!(actual.as_slice(), weights.as_slice());
+        }
+    }
+
+    mod ctrnn {
+        use super::*;
+
+        fn network() -> Network {
+            let layers = &[
+                LayerTopology::new(1),
+                LayerTopology::new(1)
+                    .with_kind(LayerKind::Ctrnn(Ctrnn::new(0.1)))
+                    .with_activation(Activation::Identity),
+            ];
+
+            // bias, tau, input weight, recurrent weight
+            Network::from_weights(layers, vec![0.5, 0.5, 1.0, 0.0])
+        }
+
+        #[test]
+        fn integrates_over_time() {
+            let mut network = network();
+
+            let outputs: Vec<_> = (0..3).map(|_| network.propagate(vec![1.0])[0]).collect();
+
+            approx::assert_relative_eq!(outputs.as_slice(), [0.7, 0.86, 0.988].as_ref());
+
+            network.reset_state();
+
+            approx::assert_relative_eq!(network.propagate(vec![1.0]).as_slice(), [0.7].as_ref());
+        }
+
+        #[test]
+        fn weights() {
+            let layers = &[
+                LayerTopology::new(2),
+                LayerTopology::new(3).with_kind(LayerKind::Ctrnn(Ctrnn::default())),
+            ];
+
+            assert_eq!(Network::<f32>::parameter_count(layers), Ok(3 * (2 + 2 + 3)));
+
+            let weights: Vec<_> = (0..21).map(|n| n as f32).collect();
+            let network: Network = Network::from_weights(layers, weights.clone());
+
+            assert_eq!(network.weights().collect::<Vec<_>>(), weights);
+            assert_eq!(network.layers[0].taus, [1.0, 8.0, 15.0]);
+        }
+
+        #[test]
+        fn random_taus_are_at_least_dt() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ctrnn = Ctrnn::new(0.2);
+
+            let network: Network = Network::random(
+                &mut rng,
+                &[
+                    LayerTopology::new(2),
+                    LayerTopology::new(4).with_kind(LayerKind::Ctrnn(ctrnn)),
+                ],
+                Initialization::default(),
+            );
+
+            assert!(network.layers[0]
+                .taus
+                .iter()
+                .all(|&tau| (ctrnn.dt..=10.0 * ctrnn.dt).contains(&tau)));
+        }
+    }
+
+    mod conv1d {
+        use super::*;
+
+        fn network(conv: Conv1d, input_size: usize, weights: Vec<f32>) -> Network {
+            let layers = &[
+                LayerTopology::new(input_size),
+                LayerTopology::new(conv.output_size(input_size).unwrap())
+                    .with_kind(LayerKind::Conv1d(conv))
+                    .with_activation(Activation::Identity),
+            ];
+
+            Network::from_weights(layers, weights)
+        }
+
+        #[test]
+        fn propagate() {
+            let mut network = network(Conv1d::new(3, 1), 5, vec![0.1, 1.0, 2.0, 3.0]);
+
+            approx::assert_relative_eq!(
+                network.propagate(vec![1.0, 2.0, 3.0, 4.0, 5.0]).as_slice(),
+                [14.1, 20.1, 26.1].as_ref()
+            );
+        }
+
+        #[test]
+        fn circular_padding() {
+            let mut network = network(
+                Conv1d::new(3, 1).with_circular_padding(),
+                5,
+                vec![0.1, 1.0, 2.0, 3.0],
+            );
+
+            approx::assert_relative_eq!(
+                network.propagate(vec![1.0, 2.0, 3.0, 4.0, 5.0]).as_slice(),
+                [13.1, 14.1, 20.1, 26.1, 17.1].as_ref()
+            );
+        }
+
+        #[test]
+        fn channels_and_stride() {
+            let mut network = network(
+                Conv1d::new(2, 2).with_stride(2),
+                4,
+                vec![0.0, 1.0, 0.0, 0.5, 0.0, -1.0],
+            );
+
+            approx::assert_relative_eq!(
+                network.propagate(vec![1.0, 2.0, 3.0, 4.0]).as_slice(),
+                [1.0, -1.5, 3.0, -3.5].as_ref()
+            );
+        }
+
+        #[test]
+        fn stacked() {
+            // Two channels per cell, reduced back to one
+            let first = Conv1d::new(1, 2);
+            let second = Conv1d::new(1, 1).with_in_channels(2);
+
+            let layers = &[
+                LayerTopology::new(3),
+                LayerTopology::new(6)
+                    .with_kind(LayerKind::Conv1d(first))
+                    .with_activation(Activation::Identity),
+                LayerTopology::new(3)
+                    .with_kind(LayerKind::Conv1d(second))
+                    .with_activation(Activation::Identity),
+            ];
+
+            let mut network: Network =
+                Network::from_weights(layers, vec![0.0, 1.0, 0.0, 2.0, 0.5, 1.0, -1.0]);
+
+            approx::assert_relative_eq!(
+                network.propagate(vec![1.0, 2.0, 3.0]).as_slice(),
+                [-0.5, -1.5, -2.5].as_ref()
+            );
+        }
+
+        #[test]
+        fn weights_are_shared() {
+            let conv = Conv1d::new(3, 4).with_circular_padding();
+
+            let layers = &[
+                LayerTopology::new(9),
+                LayerTopology::new(36).with_kind(LayerKind::Conv1d(conv)),
+            ];
+
+            assert_eq!(Network::<f32>::parameter_count(layers), Ok(16));
+
+            let weights: Vec<_> = (0..16).map(|n| n as f32).collect();
+            let network: Network = Network::from_weights(layers, weights.clone());
+
+            assert_eq!(network.output_size(), 36);
+            assert_eq!(network.weights().collect::<Vec<_>>(), weights);
+        }
+
+        #[test]
+        fn size_mismatch() {
+            let layers = &[
+                LayerTopology::new(9),
+                LayerTopology::new(9).with_kind(LayerKind::Conv1d(Conv1d::new(3, 1))),
+            ];
+
+            assert_eq!(
+                Network::<f32>::parameter_count(layers),
+                Err(NetworkError::LayerSizeMismatch {
+                    layer: 1,
+                    expected: 7,
+                    actual: 9
+                })
+            );
+        }
+
+        #[test]
+        fn kernel_wider_than_inputs() {
+            let layers = &[
+                LayerTopology::new(2),
+                LayerTopology::new(1).with_kind(LayerKind::Conv1d(Conv1d::new(3, 1))),
+            ];
+
+            assert_eq!(
+                Network::<f32>::parameter_count(layers),
+                Err(NetworkError::InvalidConvolution { layer: 1 })
+            );
+        }
+    }
+
+    mod sparse {
+        use crate::neuron::Neuron;
+
+        use super::*;
+
+        fn network() -> Network {
+            let layers = &[
+                LayerTopology::new(2),
+                LayerTopology::new(2).with_activation(Activation::Identity),
+                LayerTopology::new(1).with_activation(Activation::Identity),
+            ];
+
+            Network::from_weights(
+                layers,
+                vec![0.1, 0.5, -0.05, 0.0, 0.02, 1.0, 0.3, -0.2, 0.01],
+            )
+        }
+
+        #[test]
+        fn prune() {
+            let mut network = network();
+
+            assert_eq!(network.connections(), 6);
+            assert_eq!(network.prune(0.1), 3);
+            assert_eq!(network.connections(), 3);
+            assert_eq!(network.prune(0.1), 0);
+
+            approx::assert_relative_eq!(
+                network.weights().collect::<Vec<_>>().as_slice(),
+                [0.1, 0.5, 0.0, 0.0, 0.0, 1.0, 0.3, -0.2, 0.0].as_ref()
+            );
+
+            approx::assert_relative_eq!(
+                network.active_weights().collect::<Vec<_>>().as_slice(),
+                [0.1, 0.5, 0.0, 1.0, 0.3, -0.2].as_ref()
+            );
+        }
+
+        #[test]
+        fn propagate_skips_disconnected_inputs() {
+            let mut network: Network = Network::new(vec![
+                Layer::new(vec![
+                    Neuron::new(0.0, vec![1.0, 2.0]),
+                    Neuron::new(0.0, vec![3.0, 4.0]),
+                ])
+                .with_activation(Activation::Identity)
+                .with_mask(vec![true, false, false, true]),
+                Layer::new(vec![Neuron::new(0.0, vec![1.0, 1.0])])
+                    .with_activation(Activation::Identity),
+            ]);
+
+            approx::assert_relative_eq!(
+                network.propagate(vec![10.0, 100.0]).as_slice(),
+                [410.0].as_ref()
+            );
+        }
+
+        #[test]
+        fn set_active_weights() {
+            let mut network = network();
+            network.prune(0.1);
+
+            network
+                .set_active_weights(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+                .unwrap();
+
+            approx::assert_relative_eq!(
+                network.weights().collect::<Vec<_>>().as_slice(),
+                [1.0, 2.0, 0.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0].as_ref()
+            );
+
+            assert_eq!(
+                network.set_active_weights(vec![1.0; 9]),
+                Err(NetworkError::WeightCountMismatch {
+                    expected: 6,
+                    actual: 9
+                })
+            );
+        }
+
+        #[test]
+        fn gradient_ignores_disconnected_inputs() {
+            let mut network = network();
+            network.prune(0.1);
+
+            let (_, grad) = network.gradient(&[0.7, -0.4], &[1.0]);
+
+            for idx in [2, 4, 8] {
+                assert_eq!(grad[idx], 0.0);
+            }
+        }
+    }
+
+    mod try_new {
+        use crate::neuron::Neuron;
+
+        use super::*;
+
+        #[test]
+        fn too_few_layers() {
+            let err =
+                Network::<f32>::try_new(vec![Layer::new(vec![Neuron::new(0.1, vec![0.2])])]).err();
+
+            assert_eq!(err, Some(NetworkError::TooFewLayers { actual: 1 }));
+        }
+
+        #[test]
+        fn zero_width_layer() {
+            let err = Network::<f32>::try_new(vec![
+                Layer::new(vec![Neuron::new(0.1, vec![0.2])]),
+                Layer::new(vec![]),
+            ])
+            .err();
+
+            assert_eq!(err, Some(NetworkError::ZeroWidthLayer { layer: 2 }));
+        }
+
+        #[test]
+        fn layer_size_mismatch() {
+            let err = Network::<f32>::try_new(vec![
+                Layer::new(vec![Neuron::new(0.1, vec![0.2])]),
+                Layer::new(vec![Neuron::new(0.3, vec![0.4, 0.5, 0.6])]),
+            ])
+            .err();
+
+            assert_eq!(
+                err,
+                Some(NetworkError::LayerSizeMismatch {
+                    layer: 1,
+                    expected: 3,
+                    actual: 1
+                })
+            );
+        }
+
+        #[test]
+        fn non_finite_weight() {
+            let err = Network::<f32>::try_new(vec![
+                Layer::new(vec![Neuron::new(0.1, vec![0.2])]),
+                Layer::new(vec![Neuron::new(0.3, vec![f32::INFINITY])]),
+            ])
+            .err();
+
+            assert_eq!(
+                err,
+                Some(NetworkError::NonFiniteWeight {
+                    index: 3,
+                    value: f32::INFINITY
+                })
+            );
+        }
+    }
+
+    mod try_from_weights {
+        use super::*;
+
+        fn layers() -> [LayerTopology; 2] {
+            [LayerTopology::new(3), LayerTopology::new(2)]
+        }
+
+        #[test]
+        fn too_few_layers() {
+            let err = Network::<f32>::try_from_weights(&[LayerTopology::new(3)], vec![]).err();
+
+            assert_eq!(err, Some(NetworkError::TooFewLayers { actual: 1 }));
+        }
+
+        #[test]
+        fn parameter_count_overflow() {
+            let layers = [LayerTopology::new(1 << 40), LayerTopology::new(1 << 40)];
+            let err = Network::<f32>::try_from_weights(&layers, vec![]).err();
+
+            assert_eq!(err, Some(NetworkError::ParameterCountOverflow { layer: 1 }));
+        }
+
+        #[test]
+        fn not_enough_weights() {
+            let err = Network::<f32>::try_from_weights(&layers(), vec![0.1; 7]).err();
+
+            assert_eq!(
+                err,
+                Some(NetworkError::WeightCountMismatch {
+                    expected: 8,
+                    actual: 7
+                })
+            );
+        }
+
+        #[test]
+        fn too_many_weights() {
+            let err = Network::<f32>::try_from_weights(&layers(), vec![0.1; 9]).err();
+
+            assert_eq!(
+                err,
+                Some(NetworkError::WeightCountMismatch {
+                    expected: 8,
+                    actual: 9
+                })
+            );
+        }
+
+        #[test]
+        fn zero_width_layer() {
+            let layers = [LayerTopology::new(3), LayerTopology::new(0)];
+            let err = Network::<f32>::try_from_weights(&layers, vec![]).err();
+
+            assert_eq!(err, Some(NetworkError::ZeroWidthLayer { layer: 1 }));
+        }
+
+        #[test]
+        fn non_finite_weight() {
+            let mut weights = vec![0.1; 8];
+            weights[5] = f32::NAN;
+
+            let err = Network::<f32>::try_from_weights(&layers(), weights).err();
+
+            assert!(matches!(
+                err,
+                Some(NetworkError::NonFiniteWeight { index: 5, .. })
+            ));
+        }
     }
 
     mod weights {
@@ -115,11 +1105,14 @@ mod tests {
 
         #[test]
         fn test() {
-            let network = Network::new(vec![
-                Layer::new(vec![Neuron::new(0.1, vec![0.2, 0.3, 0.4])]),
-                Layer::new(vec![Neuron::new(0.5, vec![0.6, 0.7, 0.8])]),
+            let network: Network = Network::new(vec![
+                Layer::new(vec![
+                    Neuron::new(0.1, vec![0.2, 0.3]),
+                    Neuron::new(0.4, vec![0.5, 0.6]),
+                ]),
+                Layer::new(vec![Neuron::new(0.7, vec![0.8, 0.9])]),
             ]);
-            let expected = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+            let expected = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
 
             let actual: Vec<_> = network.weights().collect();
 
@@ -132,14 +1125,82 @@ mod tests {
 
         #[test]
         fn test() {
-            let layers = &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 2 }];
+            let layers = &[LayerTopology::new(3), LayerTopology::new(2)];
 
             let weights = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
 
-            let network = Network::from_weights(layers, weights.clone());
+            let network: Network = Network::from_weights(layers, weights.clone());
             let actual: Vec<_> = network.weights().collect();
 
             approx::assert_relative_eq!(actual.as_slice(), weights.as_slice(),);
         }
+
+        #[test]
+        fn keeps_activations() {
+            let layers = &[
+                LayerTopology::new(1),
+                LayerTopology::new(1).with_activation(Activation::Identity),
+            ];
+
+            let mut network: Network = Network::from_weights(layers, vec![-0.5, 0.25]);
+
+            approx::assert_relative_eq!(network.propagate(vec![1.0]).as_slice(), [-0.25].as_ref(),);
+        }
+    }
+
+    mod f64 {
+        use super::*;
+
+        fn layers() -> [LayerTopology; 4] {
+            [
+                LayerTopology::new(3),
+                LayerTopology::new(4)
+                    .with_kind(LayerKind::Ctrnn(Ctrnn::default()))
+                    .with_activation(Activation::Tanh),
+                LayerTopology::new(3).with_kind(LayerKind::Recurrent),
+                LayerTopology::new(2).with_activation(Activation::Sigmoid),
+            ]
+        }
+
+        #[test]
+        fn from_weights() {
+            let count = Network::<f32>::parameter_count(&layers()).unwrap();
+            let weights: Vec<f64> = (0..count).map(|n| (n as f64 * 0.7).sin()).collect();
+            let network = Network::<f64>::from_weights(&layers(), weights.clone());
+
+            assert_eq!(network.weights().collect::<Vec<_>>(), weights);
+        }
+
+        #[test]
+        fn matches_f32() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut network: Network =
+                Network::random(&mut rng, &layers(), Initialization::default());
+
+            let mut network_f64 =
+                Network::<f64>::from_weights(&network.topology(), network.weights().map(f64::from));
+
+            for inputs in [[0.5, -0.3, 0.8], [0.1, 0.9, -1.0], [0.0, 0.0, 0.0]] {
+                let expected = network.propagate(inputs.to_vec());
+                let actual = network_f64.propagate(inputs.map(f64::from).to_vec());
+
+                for (expected, actual) in expected.into_iter().zip(actual) {
+                    assert_relative_eq!(actual as f32, expected, epsilon = 1e-6);
+                }
+            }
+        }
+
+        #[test]
+        fn random() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let network = Network::<f64>::random(&mut rng, &layers(), Initialization::default());
+
+            assert_eq!(
+                network.weights().count(),
+                Network::<f32>::parameter_count(&layers()).unwrap()
+            );
+
+            assert!(network.weights().all(|w| w.abs() <= 1.0));
+        }
     }
 }
@@ -0,0 +1,510 @@
+use alloc::{vec, vec::Vec};
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use super::{ConnectionGene, InnovationTracker, NeatNetwork, NodeGene, NodeKind};
+use crate::Activation;
+
+const ADD_CONNECTION_ATTEMPTS: usize = 20;
+
+/// Inherited disabled genes stay disabled with this probability.
+const KEEP_DISABLED_CHANCE: f64 = 0.75;
+
+#[derive(Clone, Debug)]
+pub struct Genome {
+    pub(crate) inputs: usize,
+    pub(crate) outputs: usize,
+    // Sorted by id
+    pub(crate) nodes: Vec<NodeGene>,
+    // Sorted by innovation number
+    pub(crate) connections: Vec<ConnectionGene>,
+    pub(crate) hidden_activation: Activation,
+    pub(crate) output_activation: Activation,
+}
+
+impl Genome {
+    /// Creates a genome with all inputs directly connected to all outputs,
+    /// and no hidden nodes - the usual NEAT starting point.
+    ///
+    /// `inputs` and `outputs` must match the ones `tracker` was created
+    /// with, otherwise node ids handed out by it would collide with ours.
+    pub fn minimal(
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+        inputs: usize,
+        outputs: usize,
+    ) -> Self {
+        assert_eq!(inputs, tracker.inputs(), "tracker has different inputs");
+        assert_eq!(outputs, tracker.outputs(), "tracker has different outputs");
+
+        let nodes = (0..inputs + outputs)
+            .map(|id| NodeGene {
+                id,
+                kind: if id < inputs {
+                    NodeKind::Input
+                } else {
+                    NodeKind::Output
+                },
+                bias: if id < inputs {
+                    0.0
+                } else {
+                    rng.gen_range(-1.0..=1.0)
+                },
+            })
+            .collect();
+
+        let mut connections = Vec::with_capacity(inputs * outputs);
+
+        for to in inputs..inputs + outputs {
+            for from in 0..inputs {
+                connections.push(ConnectionGene {
+                    innovation: tracker.connection(from, to),
+                    from,
+                    to,
+                    weight: rng.gen_range(-1.0..=1.0),
+                    enabled: true,
+                });
+            }
+        }
+
+        connections.sort_by_key(|conn| conn.innovation);
+
+        Self {
+            inputs,
+            outputs,
+            nodes,
+            connections,
+            hidden_activation: Activation::default(),
+            output_activation: Activation::default(),
+        }
+    }
+
+    pub fn with_activations(mut self, hidden: Activation, output: Activation) -> Self {
+        self.hidden_activation = hidden;
+        self.output_activation = output;
+        self
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub fn nodes(&self) -> &[NodeGene] {
+        &self.nodes
+    }
+
+    pub fn connections(&self) -> &[ConnectionGene] {
+        &self.connections
+    }
+
+    pub fn network(&self) -> NeatNetwork {
+        NeatNetwork::new(self)
+    }
+
+    /// Returns connection weights (in the innovation order) followed by
+    /// biases of non-input nodes (in the id order).
+    pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
+        self.connections
+            .iter()
+            .map(|conn| conn.weight)
+            .chain(self.non_input_nodes().map(|node| node.bias))
+    }
+
+    /// Perturbs each weight and bias with given chance, by at most `coeff`.
+    pub fn mutate_weights(&mut self, rng: &mut dyn RngCore, chance: f32, coeff: f32) {
+        let params = self
+            .connections
+            .iter_mut()
+            .map(|conn| &mut conn.weight)
+            .chain(
+                self.nodes
+                    .iter_mut()
+                    .filter(|node| node.kind != NodeKind::Input)
+                    .map(|node| &mut node.bias),
+            );
+
+        for param in params {
+            if rng.gen_bool(chance as f64) {
+                *param += rng.gen_range(-coeff..=coeff);
+            }
+        }
+    }
+
+    /// Connects two so-far unconnected nodes; returns `false` if no
+    /// suitable pair was found.
+    ///
+    /// Connections never form cycles, so the genome always describes a
+    /// feed-forward network.
+    pub fn mutate_add_connection(
+        &mut self,
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+    ) -> bool {
+        for _ in 0..ADD_CONNECTION_ATTEMPTS {
+            let from = self.nodes.choose(rng).unwrap();
+            let to = self.nodes.choose(rng).unwrap();
+
+            if from.kind == NodeKind::Output || to.kind == NodeKind::Input || from.id == to.id {
+                continue;
+            }
+
+            let (from, to) = (from.id, to.id);
+
+            if self.has_connection(from, to) || self.reaches(to, from) {
+                continue;
+            }
+
+            self.insert_connection(ConnectionGene {
+                innovation: tracker.connection(from, to),
+                from,
+                to,
+                weight: rng.gen_range(-1.0..=1.0),
+                enabled: true,
+            });
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Splits a random enabled connection in two, with a new hidden node in
+    /// between; returns `false` if there's no connection to split.
+    ///
+    /// The incoming half gets a weight of one and the outgoing half keeps
+    /// the original weight, so the network behaves (nearly) the same as
+    /// before the mutation.
+    pub fn mutate_add_node(
+        &mut self,
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+    ) -> bool {
+        let enabled: Vec<_> = (0..self.connections.len())
+            .filter(|&idx| self.connections[idx].enabled)
+            .collect();
+
+        let Some(&idx) = enabled.choose(rng) else {
+            return false;
+        };
+
+        self.connections[idx].enabled = false;
+
+        let split = self.connections[idx].clone();
+        let node = tracker.split(split.innovation, |id| self.node(id).is_some());
+
+        let pos = self.nodes.partition_point(|n| n.id < node);
+
+        self.nodes.insert(
+            pos,
+            NodeGene {
+                id: node,
+                kind: NodeKind::Hidden,
+                bias: 0.0,
+            },
+        );
+
+        self.insert_connection(ConnectionGene {
+            innovation: tracker.connection(split.from, node),
+            from: split.from,
+            to: node,
+            weight: 1.0,
+            enabled: true,
+        });
+
+        self.insert_connection(ConnectionGene {
+            innovation: tracker.connection(node, split.to),
+            from: node,
+            to: split.to,
+            weight: split.weight,
+            enabled: true,
+        });
+
+        true
+    }
+
+    /// NEAT crossover: genes present in both parents are inherited from a
+    /// random one of them, while disjoint and excess genes come from the
+    /// fitter parent only.
+    pub fn crossover(fitter: &Self, other: &Self, rng: &mut dyn RngCore) -> Self {
+        let connections = fitter
+            .connections
+            .iter()
+            .map(|conn| {
+                let Some(other_conn) = other.connection(conn.innovation) else {
+                    return conn.clone();
+                };
+
+                let mut child = if rng.gen_bool(0.5) {
+                    conn.clone()
+                } else {
+                    other_conn.clone()
+                };
+
+                child.enabled = if conn.enabled && other_conn.enabled {
+                    true
+                } else {
+                    !rng.gen_bool(KEEP_DISABLED_CHANCE)
+                };
+
+                child
+            })
+            .collect();
+
+        let nodes = fitter
+            .nodes
+            .iter()
+            .map(|node| match other.node(node.id) {
+                Some(other_node) if rng.gen_bool(0.5) => other_node.clone(),
+                _ => node.clone(),
+            })
+            .collect();
+
+        Self {
+            inputs: fitter.inputs,
+            outputs: fitter.outputs,
+            nodes,
+            connections,
+            hidden_activation: fitter.hidden_activation.clone(),
+            output_activation: fitter.output_activation.clone(),
+        }
+    }
+
+    pub(crate) fn non_input_nodes(&self) -> impl Iterator<Item = &NodeGene> {
+        self.nodes
+            .iter()
+            .filter(|node| node.kind != NodeKind::Input)
+    }
+
+    fn node(&self, id: usize) -> Option<&NodeGene> {
+        self.nodes
+            .binary_search_by_key(&id, |node| node.id)
+            .ok()
+            .map(|idx| &self.nodes[idx])
+    }
+
+    fn connection(&self, innovation: usize) -> Option<&ConnectionGene> {
+        self.connections
+            .binary_search_by_key(&innovation, |conn| conn.innovation)
+            .ok()
+            .map(|idx| &self.connections[idx])
+    }
+
+    fn has_connection(&self, from: usize, to: usize) -> bool {
+        self.connections
+            .iter()
+            .any(|conn| conn.from == from && conn.to == to)
+    }
+
+    /// Whether there's a path `from -> ... -> to`; disabled connections
+    /// count too, since crossover might enable them again.
+    fn reaches(&self, from: usize, to: usize) -> bool {
+        let mut pending = vec![from];
+        let mut visited = Vec::new();
+
+        while let Some(node) = pending.pop() {
+            if node == to {
+                return true;
+            }
+
+            if visited.contains(&node) {
+                continue;
+            }
+
+            visited.push(node);
+
+            pending.extend(
+                self.connections
+                    .iter()
+                    .filter(|conn| conn.from == node)
+                    .map(|conn| conn.to),
+            );
+        }
+
+        false
+    }
+
+    fn insert_connection(&mut self, conn: ConnectionGene) {
+        let pos = self
+            .connections
+            .partition_point(|c| c.innovation < conn.innovation);
+
+        self.connections.insert(pos, conn);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    fn genome(rng: &mut dyn RngCore, tracker: &mut InnovationTracker) -> Genome {
+        Genome::minimal(rng, tracker, 3, 2)
+    }
+
+    mod minimal {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(3, 2);
+            let genome = genome(&mut rng, &mut tracker);
+
+            assert_eq!(genome.nodes().len(), 5);
+            assert_eq!(genome.connections().len(), 6);
+
+            let innovations: Vec<_> = genome.connections().iter().map(|c| c.innovation).collect();
+            assert_eq!(innovations, [0, 1, 2, 3, 4, 5]);
+
+            // 6 weights + 2 output biases
+            assert_eq!(genome.weights().count(), 8);
+        }
+
+        #[test]
+        #[should_panic(expected = "tracker has different outputs")]
+        fn tracker_mismatch() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(3, 1);
+
+            genome(&mut rng, &mut tracker);
+        }
+    }
+
+    mod mutate_add_node {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(3, 2);
+            let mut genome = genome(&mut rng, &mut tracker);
+
+            assert!(genome.mutate_add_node(&mut rng, &mut tracker));
+
+            assert_eq!(genome.nodes().len(), 6);
+            assert_eq!(genome.nodes()[5].kind, NodeKind::Hidden);
+            assert_eq!(genome.connections().len(), 8);
+            assert_eq!(
+                genome.connections().iter().filter(|c| !c.enabled).count(),
+                1
+            );
+            assert_eq!(genome.network().propagate(vec![0.3, -0.7, 0.9]).len(), 2);
+        }
+
+        #[test]
+        fn same_split_in_two_genomes_gets_the_same_innovations() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(1, 1);
+            let mut a = Genome::minimal(&mut rng, &mut tracker, 1, 1);
+            let mut b = Genome::minimal(&mut rng, &mut tracker, 1, 1);
+
+            a.mutate_add_node(&mut rng, &mut tracker);
+            b.mutate_add_node(&mut rng, &mut tracker);
+
+            let innovations = |genome: &Genome| {
+                genome
+                    .connections()
+                    .iter()
+                    .map(|c| (c.innovation, c.from, c.to))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(innovations(&a), innovations(&b));
+        }
+    }
+
+    mod mutate_add_connection {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(3, 2);
+            let mut genome = genome(&mut rng, &mut tracker);
+
+            // Fully connected and without hidden nodes, there's nothing
+            // left to connect
+            assert!(!genome.mutate_add_connection(&mut rng, &mut tracker));
+
+            for _ in 0..4 {
+                genome.mutate_add_node(&mut rng, &mut tracker);
+            }
+
+            let before = genome.connections().len();
+
+            assert!(genome.mutate_add_connection(&mut rng, &mut tracker));
+            assert_eq!(genome.connections().len(), before + 1);
+
+            for conn in genome.connections() {
+                assert!(!genome.reaches(conn.to, conn.from));
+            }
+        }
+    }
+
+    mod crossover {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(3, 2);
+            let mut fitter = genome(&mut rng, &mut tracker);
+            let mut other = genome(&mut rng, &mut tracker);
+
+            fitter.mutate_add_node(&mut rng, &mut tracker);
+            other.mutate_add_node(&mut rng, &mut tracker);
+            other.mutate_add_node(&mut rng, &mut tracker);
+
+            let child = Genome::crossover(&fitter, &other, &mut rng);
+
+            // Structure comes from the fitter parent ...
+            let structure = |genome: &Genome| {
+                genome
+                    .connections()
+                    .iter()
+                    .map(|c| c.innovation)
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(structure(&child), structure(&fitter));
+            assert_eq!(child.nodes().len(), fitter.nodes().len());
+
+            // ... while matching genes are mixed
+            let from_other = child
+                .connections()
+                .iter()
+                .filter(|c| other.connection(c.innovation).map(|o| o.weight) == Some(c.weight))
+                .count();
+
+            assert!(from_other > 0);
+            assert!(from_other < child.connections().len());
+        }
+    }
+
+    mod mutate_weights {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut tracker = InnovationTracker::new(3, 2);
+            let mut genome = genome(&mut rng, &mut tracker);
+            let before: Vec<_> = genome.weights().collect();
+
+            genome.mutate_weights(&mut rng, 1.0, 0.5);
+
+            let after: Vec<_> = genome.weights().collect();
+
+            for (before, after) in before.iter().zip(&after) {
+                assert_ne!(before, after);
+                assert!((before - after).abs() <= 0.5);
+            }
+        }
+    }
+}
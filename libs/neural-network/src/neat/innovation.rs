@@ -0,0 +1,95 @@
+use alloc::collections::BTreeMap;
+
+/// Hands out innovation numbers and node ids.
+///
+/// The same structural change (a connection between the same two nodes,
+/// or a split of the same connection) always gets the same number, no
+/// matter which genome it happens in - that's what lets `Genome::crossover()`
+/// line up genes of different genomes.
+#[derive(Clone, Debug)]
+pub struct InnovationTracker {
+    inputs: usize,
+    outputs: usize,
+    next_innovation: usize,
+    next_node: usize,
+    connections: BTreeMap<(usize, usize), usize>,
+    splits: BTreeMap<usize, usize>,
+}
+
+impl InnovationTracker {
+    /// Node ids `0..inputs` are reserved for inputs, and the following
+    /// `outputs` ids for outputs.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        Self {
+            inputs,
+            outputs,
+            next_innovation: 0,
+            next_node: inputs + outputs,
+            connections: BTreeMap::new(),
+            splits: BTreeMap::new(),
+        }
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub(crate) fn connection(&mut self, from: usize, to: usize) -> usize {
+        let next = &mut self.next_innovation;
+
+        *self.connections.entry((from, to)).or_insert_with(|| {
+            *next += 1;
+            *next - 1
+        })
+    }
+
+    /// Returns id of the node that splits given connection.
+    ///
+    /// `taken` is consulted when the split is already known: a genome that
+    /// got the very same connection re-enabled and split again needs a
+    /// brand new node.
+    pub(crate) fn split(&mut self, innovation: usize, taken: impl Fn(usize) -> bool) -> usize {
+        match self.splits.get(&innovation) {
+            Some(&node) if !taken(node) => node,
+            Some(_) => self.node(),
+            None => {
+                let node = self.node();
+                self.splits.insert(innovation, node);
+                node
+            }
+        }
+    }
+
+    fn node(&mut self) -> usize {
+        self.next_node += 1;
+        self.next_node - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_connection_innovations() {
+        let mut tracker = InnovationTracker::new(2, 1);
+
+        assert_eq!(tracker.connection(0, 2), 0);
+        assert_eq!(tracker.connection(1, 2), 1);
+        assert_eq!(tracker.connection(0, 2), 0);
+    }
+
+    #[test]
+    fn reuses_split_nodes() {
+        let mut tracker = InnovationTracker::new(2, 1);
+
+        assert_eq!(tracker.split(0, |_| false), 3);
+        assert_eq!(tracker.split(1, |_| false), 4);
+        assert_eq!(tracker.split(0, |_| false), 3);
+        assert_eq!(tracker.split(0, |node| node == 3), 5);
+    }
+}
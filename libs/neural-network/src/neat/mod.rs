@@ -0,0 +1,36 @@
+//! NEAT-style networks, whose topology evolves alongside their weights.
+//!
+//! A `Genome` is a list of node and connection genes; each connection
+//! carries an innovation number handed out by an `InnovationTracker`, so
+//! that genomes with different histories can still be lined up during
+//! crossover. Genomes are turned into runnable `NeatNetwork`s with
+//! `Genome::network()`.
+
+pub use self::{genome::Genome, innovation::InnovationTracker, network::NeatNetwork};
+
+mod genome;
+mod innovation;
+mod network;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Input,
+    Hidden,
+    Output,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeGene {
+    pub id: usize,
+    pub kind: NodeKind,
+    pub bias: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionGene {
+    pub innovation: usize,
+    pub from: usize,
+    pub to: usize,
+    pub weight: f32,
+    pub enabled: bool,
+}
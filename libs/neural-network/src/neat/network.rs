@@ -0,0 +1,255 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use super::{Genome, NodeKind};
+use crate::{Activation, PropagationBuffer};
+
+/// Runnable form of a `Genome`.
+///
+/// Values of all nodes live in one flat array: inputs come first, followed
+/// by the remaining nodes in an order where every node comes after all of
+/// its sources.
+#[derive(Clone, Debug)]
+pub struct NeatNetwork {
+    inputs: usize,
+    nodes: Vec<Node>,
+    // (source slot, weight), grouped by target node
+    edges: Vec<(usize, f32)>,
+    outputs: Vec<usize>,
+    hidden_activation: Activation,
+    output_activation: Activation,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    kind: NodeKind,
+    bias: f32,
+    edges: core::ops::Range<usize>,
+}
+
+impl NeatNetwork {
+    pub(crate) fn new(genome: &Genome) -> Self {
+        let order = Self::sort(genome);
+
+        let slots: BTreeMap<_, _> = (0..genome.inputs)
+            .chain(order.iter().map(|node| node.id))
+            .enumerate()
+            .map(|(slot, id)| (id, slot))
+            .collect();
+
+        let mut edges = Vec::new();
+
+        let nodes = order
+            .iter()
+            .map(|node| {
+                let start = edges.len();
+
+                edges.extend(
+                    genome
+                        .connections
+                        .iter()
+                        .filter(|conn| conn.enabled && conn.to == node.id)
+                        .map(|conn| (slots[&conn.from], conn.weight)),
+                );
+
+                Node {
+                    kind: node.kind,
+                    bias: node.bias,
+                    edges: start..edges.len(),
+                }
+            })
+            .collect();
+
+        let outputs = genome
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Output)
+            .map(|node| slots[&node.id])
+            .collect();
+
+        Self {
+            inputs: genome.inputs,
+            nodes,
+            edges,
+            outputs,
+            hidden_activation: genome.hidden_activation.clone(),
+            output_activation: genome.output_activation.clone(),
+        }
+    }
+
+    /// Orders non-input nodes topologically (Kahn's algorithm), breaking
+    /// ties by node id.
+    fn sort(genome: &Genome) -> Vec<&super::NodeGene> {
+        let mut pending: Vec<_> = genome.non_input_nodes().collect();
+        let mut order = Vec::with_capacity(pending.len());
+
+        let is_ready = |id: usize, order: &[&super::NodeGene]| {
+            genome
+                .connections
+                .iter()
+                .filter(|conn| conn.enabled && conn.to == id && conn.from >= genome.inputs)
+                .all(|conn| order.iter().any(|node| node.id == conn.from))
+        };
+
+        while !pending.is_empty() {
+            let next = pending
+                .iter()
+                .position(|node| is_ready(node.id, &order))
+                // Genomes are acyclic, but just in case - rather than
+                // looping forever, evaluate the remaining nodes in the id
+                // order, treating not-yet-computed sources as zeros
+                .unwrap_or(0);
+
+            order.push(pending.remove(next));
+        }
+
+        order
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        let mut scratch = PropagationBuffer::default();
+        let mut outputs = vec![0.0; self.output_size()];
+
+        self.propagate_into(&inputs, &mut scratch, &mut outputs);
+        outputs
+    }
+
+    pub fn propagate_into(&self, inputs: &[f32], scratch: &mut PropagationBuffer, out: &mut [f32]) {
+        assert_eq!(inputs.len(), self.inputs);
+        assert_eq!(out.len(), self.output_size());
+
+        let values = self.evaluate(inputs, scratch);
+
+        for (out, &slot) in out.iter_mut().zip(&self.outputs) {
+            *out = values[slot];
+        }
+    }
+
+    /// Returns the inputs, the hidden nodes' activations (in evaluation
+    /// order) and the outputs.
+    pub fn propagate_traced(&self, inputs: Vec<f32>) -> Vec<Vec<f32>> {
+        let mut scratch = PropagationBuffer::default();
+        let values = self.evaluate(&inputs, &mut scratch);
+
+        let hidden = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.kind == NodeKind::Hidden)
+            .map(|(idx, _)| values[self.inputs + idx])
+            .collect();
+
+        let outputs = self.outputs.iter().map(|&slot| values[slot]).collect();
+
+        vec![inputs, hidden, outputs]
+    }
+
+    fn evaluate<'a>(&self, inputs: &[f32], scratch: &'a mut PropagationBuffer) -> &'a [f32] {
+        let values = scratch.reserve(self.inputs + self.nodes.len());
+
+        values[..self.inputs].copy_from_slice(inputs);
+        values[self.inputs..].fill(0.0);
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let sum = self.edges[node.edges.clone()]
+                .iter()
+                .map(|&(slot, weight)| values[slot] * weight)
+                .sum::<f32>();
+
+            let activation = match node.kind {
+                NodeKind::Output => &self.output_activation,
+                _ => &self.hidden_activation,
+            };
+
+            values[self.inputs + idx] = activation.apply(sum + node.bias);
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neat::{ConnectionGene, NodeGene};
+
+    fn node(id: usize, kind: NodeKind, bias: f32) -> NodeGene {
+        NodeGene { id, kind, bias }
+    }
+
+    fn conn(innovation: usize, from: usize, to: usize, weight: f32) -> ConnectionGene {
+        ConnectionGene {
+            innovation,
+            from,
+            to,
+            weight,
+            enabled: true,
+        }
+    }
+
+    //   0 ──────────────┐
+    //   │               v
+    //   └──> 3 ──> 4 ──> 2
+    //   1 ───^
+    fn genome() -> Genome {
+        Genome {
+            inputs: 2,
+            outputs: 1,
+            nodes: vec![
+                node(0, NodeKind::Input, 0.0),
+                node(1, NodeKind::Input, 0.0),
+                node(2, NodeKind::Output, 0.1),
+                node(3, NodeKind::Hidden, 0.2),
+                node(4, NodeKind::Hidden, -0.3),
+            ],
+            connections: vec![
+                conn(0, 0, 2, 0.5),
+                conn(1, 0, 3, 1.0),
+                conn(2, 1, 3, -1.0),
+                conn(3, 4, 2, 2.0),
+                conn(4, 3, 4, 0.5),
+            ],
+            hidden_activation: Activation::ReLU,
+            output_activation: Activation::Identity,
+        }
+    }
+
+    #[test]
+    fn propagate() {
+        let network = genome().network();
+
+        let h3 = (1.0f32 * 1.0 - 1.0 * 0.5 + 0.2).max(0.0);
+        let h4 = (h3 * 0.5 - 0.3).max(0.0);
+        let out = 1.0 * 0.5 + h4 * 2.0 + 0.1;
+
+        approx::assert_relative_eq!(network.propagate(vec![1.0, 0.5]).as_slice(), [out].as_ref());
+    }
+
+    #[test]
+    fn disabled_connections_are_skipped() {
+        let mut genome = genome();
+        genome.connections[3].enabled = false;
+
+        approx::assert_relative_eq!(
+            genome.network().propagate(vec![1.0, 0.5]).as_slice(),
+            [0.6].as_ref()
+        );
+    }
+
+    #[test]
+    fn propagate_traced() {
+        let trace = genome().network().propagate_traced(vec![1.0, 0.5]);
+
+        assert_eq!(trace.len(), 3);
+        assert_eq!(trace[0], vec![1.0, 0.5]);
+        assert_eq!(trace[1].len(), 2);
+        assert_eq!(trace[2], genome().network().propagate(vec![1.0, 0.5]));
+    }
+}
@@ -1,37 +1,30 @@
+use alloc::vec::Vec;
+
 use rand::Rng;
 
-pub struct Neuron {
-    pub(crate) bias: f32,
-    pub(crate) weights: Vec<f32>,
+use crate::{activation::Activation, Float};
+
+pub struct Neuron<F = f32> {
+    pub(crate) bias: F,
+    pub(crate) weights: Vec<F>,
 }
 
-impl Neuron {
-    pub fn new(bias: f32, weights: Vec<f32>) -> Self {
+impl<F: Float> Neuron<F> {
+    pub fn new(bias: F, weights: Vec<F>) -> Self {
         Self { bias, weights }
     }
 
-    pub fn propagate(&self, inputs: &[f32]) -> f32 {
-        assert!(inputs.len() == self.weights.len());
-        (inputs
-            .iter()
-            .zip(self.weights.iter())
-            .map(|(input, weight)| input * weight)
-            .sum::<f32>()
-            + self.bias)
-            .max(0.0)
-    }
-
     pub fn random(rng: &mut dyn rand::RngCore, input_neurons: usize) -> Self {
-        let bias = rng.gen_range(-1.0..=1.0);
+        let bias = rng.gen_range(-F::one()..=F::one());
 
         let weights = (0..input_neurons)
-            .map(|_| rng.gen_range(-1.0..=1.0))
+            .map(|_| rng.gen_range(-F::one()..=F::one()))
             .collect();
 
         Self { bias, weights }
     }
 
-    pub fn from_weights(output_neurons: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
+    pub fn from_weights(output_neurons: usize, weights: &mut dyn Iterator<Item = F>) -> Self {
         let bias = weights.next().expect("got not enough weights");
 
         let weights = (0..output_neurons)
@@ -40,4 +33,16 @@ impl Neuron {
 
         Self { bias, weights }
     }
-}
\ No newline at end of file
+
+    pub fn propagate(&self, inputs: &[F], activation: &Activation) -> F {
+        assert!(inputs.len() == self.weights.len());
+        activation.apply(
+            inputs
+                .iter()
+                .zip(self.weights.iter())
+                .map(|(&input, &weight)| input * weight)
+                .sum::<F>()
+                + self.bias,
+        )
+    }
+}
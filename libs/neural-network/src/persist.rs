@@ -0,0 +1,634 @@
+//! Saving and loading networks.
+//!
+//! Two formats are supported, both carrying the same data (format version,
+//! topology with activations and masks, and weights in the
+//! `Network::weights()` order):
+//!
+//! - `Format::Json` - meant for humans and diffing,
+//! - `Format::Binary` - a `FLNN` magic and a little-endian version number,
+//!   followed by the bincode-encoded network.
+//!
+//! Version 1 files (from before masks got saved) can still be loaded.
+
+use std::{fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Activation, Conv1d, Ctrnn, LayerKind, LayerTopology, Network, NetworkError};
+
+pub const FORMAT_VERSION: u32 = 2;
+
+const MAGIC: &[u8; 4] = b"FLNN";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Binary,
+}
+
+#[derive(Debug)]
+pub enum PersistError {
+    Io(io::Error),
+    Malformed(String),
+    Truncated,
+    UnsupportedVersion(u64),
+    /// `Activation::Custom` closures cannot be saved.
+    UnsupportedActivation,
+    TopologyMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    Invalid(NetworkError),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "i/o error: {}", err),
+            Self::Malformed(msg) => write!(f, "malformed network: {}", msg),
+            Self::Truncated => write!(f, "network data is truncated"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version: {}", version)
+            }
+            Self::UnsupportedActivation => write!(f, "custom activations cannot be saved"),
+            Self::TopologyMismatch { expected, actual } => write!(
+                f,
+                "topology expects {} weights, but got {}",
+                expected, actual
+            ),
+            Self::Invalid(err) => write!(f, "invalid network: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            Self::Truncated
+        } else {
+            Self::Io(err)
+        }
+    }
+}
+
+impl From<NetworkError> for PersistError {
+    fn from(err: NetworkError) -> Self {
+        match err {
+            NetworkError::WeightCountMismatch { expected, actual } => {
+                Self::TopologyMismatch { expected, actual }
+            }
+            err => Self::Invalid(err),
+        }
+    }
+}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(err: serde_json::Error) -> Self {
+        if err.is_eof() {
+            Self::Truncated
+        } else if err.is_io() {
+            Self::Io(err.into())
+        } else {
+            Self::Malformed(err.to_string())
+        }
+    }
+}
+
+impl From<bincode::Error> for PersistError {
+    fn from(err: bincode::Error) -> Self {
+        match *err {
+            bincode::ErrorKind::Io(err) => err.into(),
+            err => Self::Malformed(err.to_string()),
+        }
+    }
+}
+
+impl Network {
+    pub fn save(&self, format: Format, mut writer: impl io::Write) -> Result<(), PersistError> {
+        let repr = NetworkRepr::new(self)?;
+
+        match format {
+            Format::Json => serde_json::to_writer_pretty(writer, &repr)?,
+            Format::Binary => {
+                writer.write_all(MAGIC)?;
+                writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+                bincode::serialize_into(writer, &repr)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn load(format: Format, mut reader: impl io::Read) -> Result<Self, PersistError> {
+        let repr: NetworkRepr = match format {
+            Format::Json => {
+                let value: serde_json::Value = serde_json::from_reader(reader)?;
+
+                let version = value
+                    .get("version")
+                    .and_then(|version| version.as_u64())
+                    .ok_or_else(|| PersistError::Malformed("missing version".into()))?;
+
+                match check_version(version)? {
+                    1 => serde_json::from_value::<NetworkReprV1>(value)?.into(),
+                    _ => serde_json::from_value(value)?,
+                }
+            }
+
+            Format::Binary => {
+                let mut header = [0; 8];
+                reader.read_exact(&mut header)?;
+
+                if &header[..4] != MAGIC {
+                    return Err(PersistError::Malformed("not a network file".into()));
+                }
+
+                let version = u32::from_le_bytes(header[4..].try_into().unwrap());
+
+                match check_version(version.into())? {
+                    1 => bincode::deserialize_from::<_, NetworkReprV1>(reader)?.into(),
+                    _ => bincode::deserialize_from(reader)?,
+                }
+            }
+        };
+
+        repr.into_network()
+    }
+}
+
+fn check_version(version: u64) -> Result<u32, PersistError> {
+    u32::try_from(version)
+        .ok()
+        .filter(|version| (1..=FORMAT_VERSION).contains(version))
+        .ok_or(PersistError::UnsupportedVersion(version))
+}
+
+#[derive(Serialize, Deserialize)]
+struct NetworkRepr {
+    version: u32,
+    layers: Vec<LayerRepr>,
+    weights: Vec<f32>,
+}
+
+impl NetworkRepr {
+    fn new(network: &Network) -> Result<Self, PersistError> {
+        let layers = network
+            .topology()
+            .iter()
+            .map(LayerRepr::new)
+            .collect::<Result<_, _>>()?;
+
+        let mut repr = Self {
+            version: FORMAT_VERSION,
+            layers,
+            weights: network.weights().collect(),
+        };
+
+        // The first entry describes the inputs, which have no weights
+        for (repr, layer) in repr.layers.iter_mut().skip(1).zip(&network.layers) {
+            repr.mask = layer.mask.clone();
+        }
+
+        Ok(repr)
+    }
+
+    fn into_network(self) -> Result<Network, PersistError> {
+        let mut layers = Vec::new();
+        let mut masks = Vec::new();
+
+        for layer in self.layers {
+            masks.push(layer.mask.clone());
+            layers.push(layer.into_topology()?);
+        }
+
+        let mut network = Network::try_from_weights(&layers, self.weights)?;
+
+        if masks[0].is_some() {
+            return Err(PersistError::Malformed("inputs cannot be masked".into()));
+        }
+
+        for (layer, mask) in network.layers.iter_mut().zip(masks.into_iter().skip(1)) {
+            if let Some(mask) = mask {
+                if mask.len() != layer.weights.len() {
+                    return Err(PersistError::Malformed(format!(
+                        "mask has {} entries, but its layer has {} weights",
+                        mask.len(),
+                        layer.weights.len()
+                    )));
+                }
+
+                layer.set_mask(mask);
+            }
+        }
+
+        Ok(network)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LayerRepr {
+    neurons: usize,
+    activation: ActivationRepr,
+    kind: KindRepr,
+    mask: Option<Vec<bool>>,
+}
+
+impl LayerRepr {
+    fn new(topology: &LayerTopology) -> Result<Self, PersistError> {
+        let activation = match topology.activation {
+            Activation::ReLU => ActivationRepr::Relu,
+            Activation::LeakyReLU(slope) => ActivationRepr::LeakyRelu(slope),
+            Activation::Tanh => ActivationRepr::Tanh,
+            Activation::Sigmoid => ActivationRepr::Sigmoid,
+            Activation::Identity => ActivationRepr::Identity,
+            Activation::Custom(_) => return Err(PersistError::UnsupportedActivation),
+        };
+
+        let kind = match topology.kind {
+            LayerKind::Dense => KindRepr::Dense,
+            LayerKind::Recurrent => KindRepr::Recurrent,
+            LayerKind::Conv1d(conv) => KindRepr::Conv1d {
+                kernel: conv.kernel,
+                stride: conv.stride,
+                channels: conv.channels,
+                in_channels: conv.in_channels,
+                circular: conv.circular,
+            },
+            LayerKind::Ctrnn(ctrnn) => KindRepr::Ctrnn { dt: ctrnn.dt },
+        };
+
+        Ok(Self {
+            neurons: topology.neurons,
+            activation,
+            kind,
+            mask: None,
+        })
+    }
+
+    fn into_topology(self) -> Result<LayerTopology, PersistError> {
+        let activation = match self.activation {
+            ActivationRepr::Relu => Activation::ReLU,
+            ActivationRepr::LeakyRelu(slope) => Activation::LeakyReLU(slope),
+            ActivationRepr::Tanh => Activation::Tanh,
+            ActivationRepr::Sigmoid => Activation::Sigmoid,
+            ActivationRepr::Identity => Activation::Identity,
+        };
+
+        let kind = match self.kind {
+            KindRepr::Dense => LayerKind::Dense,
+            KindRepr::Recurrent => LayerKind::Recurrent,
+            KindRepr::Conv1d {
+                kernel,
+                stride,
+                channels,
+                in_channels,
+                circular,
+            } => LayerKind::Conv1d(Conv1d {
+                kernel,
+                stride,
+                channels,
+                in_channels,
+                circular,
+            }),
+            KindRepr::Ctrnn { dt } if dt.is_nan() || dt <= 0.0 => {
+                return Err(PersistError::Malformed(format!(
+                    "CTRNN's dt must be positive, got {}",
+                    dt
+                )));
+            }
+            KindRepr::Ctrnn { dt } => LayerKind::Ctrnn(Ctrnn::new(dt)),
+        };
+
+        Ok(LayerTopology::new(self.neurons)
+            .with_activation(activation)
+            .with_kind(kind))
+    }
+}
+
+#[derive(Deserialize)]
+struct NetworkReprV1 {
+    version: u32,
+    layers: Vec<LayerReprV1>,
+    weights: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct LayerReprV1 {
+    neurons: usize,
+    activation: ActivationRepr,
+    kind: KindRepr,
+}
+
+impl From<NetworkReprV1> for NetworkRepr {
+    fn from(repr: NetworkReprV1) -> Self {
+        let layers = repr
+            .layers
+            .into_iter()
+            .map(|layer| LayerRepr {
+                neurons: layer.neurons,
+                activation: layer.activation,
+                kind: layer.kind,
+                mask: None,
+            })
+            .collect();
+
+        Self {
+            version: repr.version,
+            layers,
+            weights: repr.weights,
+        }
+    }
+}
+
+// Kept separate from the public types, so that they can evolve without
+// silently changing the on-disk format.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ActivationRepr {
+    Relu,
+    LeakyRelu(f32),
+    Tanh,
+    Sigmoid,
+    Identity,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KindRepr {
+    Dense,
+    Recurrent,
+    Conv1d {
+        kernel: usize,
+        stride: usize,
+        channels: usize,
+        in_channels: usize,
+        circular: bool,
+    },
+    Ctrnn {
+        dt: f32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Layer;
+
+    fn network() -> Network {
+        let layers = &[
+            LayerTopology::new(2),
+            LayerTopology::new(2).with_kind(LayerKind::Recurrent),
+            LayerTopology::new(1).with_activation(Activation::LeakyReLU(0.1)),
+        ];
+
+        Network::from_weights(layers, (0..13).map(|n| n as f32 / 10.0))
+    }
+
+    fn save(network: &Network, format: Format) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        network.save(format, &mut bytes).unwrap();
+        bytes
+    }
+
+    mod roundtrip {
+        use super::*;
+
+        fn check(format: Format) {
+            let network = network();
+            let loaded = Network::load(format, save(&network, format).as_slice()).unwrap();
+
+            let expected: Vec<_> = network.weights().collect();
+            let actual: Vec<_> = loaded.weights().collect();
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+            assert_eq!(
+                format!("{:?}", loaded.topology()),
+                format!("{:?}", network.topology())
+            );
+        }
+
+        #[test]
+        fn json() {
+            check(Format::Json);
+        }
+
+        #[test]
+        fn binary() {
+            check(Format::Binary);
+        }
+
+        fn check_pruned(format: Format) {
+            let mut network = network();
+            assert!(network.prune(0.35) > 0);
+
+            let mut loaded = Network::load(format, save(&network, format).as_slice()).unwrap();
+
+            assert_eq!(loaded.connections(), network.connections());
+            assert_eq!(
+                loaded.layers.iter().map(Layer::mask).collect::<Vec<_>>(),
+                network.layers.iter().map(Layer::mask).collect::<Vec<_>>()
+            );
+
+            // Pruned weights stay disconnected
+            let input = vec![1.0, 1.0];
+            assert_eq!(loaded.propagate(input.clone()), network.propagate(input));
+        }
+
+        #[test]
+        fn pruned_json() {
+            check_pruned(Format::Json);
+        }
+
+        #[test]
+        fn pruned_binary() {
+            check_pruned(Format::Binary);
+        }
+    }
+
+    mod load {
+        use super::*;
+
+        #[test]
+        fn version_1() {
+            let json = r#"{
+                "version": 1,
+                "layers": [
+                    { "neurons": 2, "activation": "relu", "kind": "dense" },
+                    { "neurons": 1, "activation": "tanh", "kind": "dense" }
+                ],
+                "weights": [0.1, 0.2, 0.3]
+            }"#;
+
+            let network = Network::load(Format::Json, json.as_bytes()).unwrap();
+
+            assert_eq!(network.weights().collect::<Vec<_>>(), [0.1, 0.2, 0.3]);
+            assert_eq!(network.connections(), 2);
+        }
+
+        #[test]
+        fn invalid_ctrnn_dt() {
+            for dt in ["0.0", "-0.1"] {
+                let json = format!(
+                    r#"{{
+                        "version": 2,
+                        "layers": [
+                            {{ "neurons": 1, "activation": "relu", "kind": "dense", "mask": null }},
+                            {{ "neurons": 1, "activation": "tanh", "kind": {{ "ctrnn": {{ "dt": {} }} }}, "mask": null }}
+                        ],
+                        "weights": [0.1, 0.2, 0.3, 1.0]
+                    }}"#,
+                    dt
+                );
+
+                let err = Network::load(Format::Json, json.as_bytes()).err();
+
+                assert!(matches!(err, Some(PersistError::Malformed(_))), "{:?}", err);
+            }
+        }
+
+        #[test]
+        fn nan_ctrnn_dt() {
+            let layers = &[
+                LayerTopology::new(1),
+                LayerTopology::new(1).with_kind(LayerKind::Ctrnn(Ctrnn::default())),
+            ];
+            let mut network: Network = Network::from_weights(layers, vec![0.1, 0.2, 0.3, 1.0]);
+
+            if let LayerKind::Ctrnn(ctrnn) = &mut network.layers[0].kind {
+                ctrnn.dt = f32::NAN;
+            }
+
+            let bytes = save(&network, Format::Binary);
+            let err = Network::load(Format::Binary, bytes.as_slice()).err();
+
+            assert!(matches!(err, Some(PersistError::Malformed(_))), "{:?}", err);
+        }
+
+        #[test]
+        fn mismatched_mask() {
+            let json = r#"{
+                "version": 2,
+                "layers": [
+                    { "neurons": 2, "activation": "relu", "kind": "dense", "mask": null },
+                    { "neurons": 1, "activation": "tanh", "kind": "dense", "mask": [true] }
+                ],
+                "weights": [0.1, 0.2, 0.3]
+            }"#;
+
+            let err = Network::load(Format::Json, json.as_bytes()).err();
+
+            assert!(matches!(err, Some(PersistError::Malformed(_))));
+        }
+
+        #[test]
+        fn truncated_json() {
+            let bytes = save(&network(), Format::Json);
+            let err = Network::load(Format::Json, &bytes[..bytes.len() / 2]).err();
+
+            assert!(matches!(err, Some(PersistError::Truncated)));
+        }
+
+        #[test]
+        fn truncated_binary() {
+            let bytes = save(&network(), Format::Binary);
+            let err = Network::load(Format::Binary, &bytes[..bytes.len() - 1]).err();
+
+            assert!(matches!(err, Some(PersistError::Truncated)));
+        }
+
+        #[test]
+        fn topology_mismatch() {
+            let json = r#"{
+                "version": 1,
+                "layers": [
+                    { "neurons": 2, "activation": "relu", "kind": "dense" },
+                    { "neurons": 1, "activation": "tanh", "kind": "dense" }
+                ],
+                "weights": [0.1, 0.2]
+            }"#;
+
+            let err = Network::load(Format::Json, json.as_bytes()).err();
+
+            assert!(matches!(
+                err,
+                Some(PersistError::TopologyMismatch {
+                    expected: 3,
+                    actual: 2
+                })
+            ));
+        }
+
+        #[test]
+        fn parameter_count_overflow() {
+            let json = r#"{
+                "version": 2,
+                "layers": [
+                    { "neurons": 1000000000000, "activation": "relu", "kind": "dense" },
+                    { "neurons": 1000000000000, "activation": "tanh", "kind": "dense" }
+                ],
+                "weights": []
+            }"#;
+
+            let err = Network::load(Format::Json, json.as_bytes()).err();
+
+            assert!(matches!(
+                err,
+                Some(PersistError::Invalid(
+                    NetworkError::ParameterCountOverflow { layer: 1 }
+                ))
+            ));
+        }
+
+        #[test]
+        fn version_overflow() {
+            // Would wrap around to 1 if truncated to `u32`
+            let json = r#"{ "version": 4294967297, "layers": [], "weights": [] }"#;
+
+            let err = Network::load(Format::Json, json.as_bytes()).err();
+
+            assert!(matches!(
+                err,
+                Some(PersistError::UnsupportedVersion(4294967297))
+            ));
+        }
+
+        #[test]
+        fn unsupported_version() {
+            let mut bytes = save(&network(), Format::Binary);
+            bytes[4] = 99;
+
+            let err = Network::load(Format::Binary, bytes.as_slice()).err();
+
+            assert!(matches!(err, Some(PersistError::UnsupportedVersion(99))));
+        }
+    }
+
+    mod save {
+        use super::*;
+
+        #[test]
+        fn custom_activation() {
+            let layers = &[
+                LayerTopology::new(1),
+                LayerTopology::new(1).with_activation(Activation::custom(|x| x)),
+            ];
+            let network: Network = Network::from_weights(layers, vec![0.0, 1.0]);
+
+            let err = network.save(Format::Json, Vec::new()).err();
+
+            assert!(matches!(err, Some(PersistError::UnsupportedActivation)));
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Inference-only networks with integer weights.
+//!
+//! Each layer keeps one scale factor, such that `weight ≈ quantized * scale`;
+//! biases, time constants, inputs and activations stay as `f32`, so a `QuantizedNetwork`
+//! takes exactly the same inputs as the network it was converted from.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{layer::Layer, Activation, LayerKind, Network, PropagationBuffer};
+
+pub trait QuantizedWeight: Copy + Into<f32> {
+    /// Largest magnitude that gets used, symmetrically around zero.
+    const MAX: f32;
+
+    /// Rounds (and clamps) an already scaled weight.
+    fn quantize(weight: f32) -> Self;
+}
+
+impl QuantizedWeight for i8 {
+    const MAX: f32 = i8::MAX as f32;
+
+    fn quantize(weight: f32) -> Self {
+        let max = <Self as QuantizedWeight>::MAX;
+        num_traits::Float::round(weight).clamp(-max, max) as i8
+    }
+}
+
+impl QuantizedWeight for i16 {
+    const MAX: f32 = i16::MAX as f32;
+
+    fn quantize(weight: f32) -> Self {
+        let max = <Self as QuantizedWeight>::MAX;
+        num_traits::Float::round(weight).clamp(-max, max) as i16
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct QuantizedNetwork<W = i8> {
+    layers: Vec<QuantizedLayer<W>>,
+}
+
+#[derive(Clone, Debug)]
+struct QuantizedLayer<W> {
+    kind: LayerKind,
+    inputs: usize,
+    outputs: usize,
+    row_len: usize,
+    scale: f32,
+    biases: Vec<f32>,
+    weights: Vec<W>,
+    recurrent: Vec<W>,
+    taus: Vec<f32>,
+    state: Vec<f32>,
+    activation: Activation,
+    /// Largest difference between an original weight and its dequantized
+    /// counterpart.
+    error: f32,
+}
+
+/// How far a `QuantizedNetwork` strays from its original.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizationReport {
+    /// Per layer, the scale factor - quantized weights are off by at most
+    /// half of it.
+    pub scales: Vec<f32>,
+    /// Per layer, the largest actual weight error.
+    pub weight_errors: Vec<f32>,
+    /// Largest and mean absolute difference between the outputs, over all
+    /// the samples.
+    pub max_output_error: f32,
+    pub mean_output_error: f32,
+}
+
+impl Network {
+    pub fn quantize<W: QuantizedWeight>(&self) -> QuantizedNetwork<W> {
+        QuantizedNetwork::new(self)
+    }
+}
+
+impl<W: QuantizedWeight> QuantizedNetwork<W> {
+    pub fn new(network: &Network) -> Self {
+        Self {
+            layers: network.layers.iter().map(QuantizedLayer::new).collect(),
+        }
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.layers[0].inputs
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.layers[self.layers.len() - 1].outputs
+    }
+
+    pub fn propagate(&mut self, inputs: Vec<f32>) -> Vec<f32> {
+        let mut scratch = PropagationBuffer::default();
+        let mut outputs = vec![0.0; self.output_size()];
+
+        self.propagate_into(&inputs, &mut scratch, &mut outputs);
+        outputs
+    }
+
+    /// Allocation-free counterpart of `propagate()`; see
+    /// `Network::propagate_into()`.
+    pub fn propagate_into(
+        &mut self,
+        inputs: &[f32],
+        scratch: &mut PropagationBuffer,
+        out: &mut [f32],
+    ) {
+        assert_eq!(inputs.len(), self.input_size());
+
+        scratch.fit(
+            self.layers
+                .iter()
+                .map(|layer| layer.inputs.max(layer.outputs))
+                .max()
+                .unwrap_or(0),
+        );
+
+        let (last, hidden) = self.layers.split_last_mut().unwrap();
+
+        scratch.front[..inputs.len()].copy_from_slice(inputs);
+
+        for layer in hidden {
+            layer.propagate_into(
+                &scratch.front[..layer.inputs],
+                &mut scratch.back[..layer.outputs],
+            );
+
+            core::mem::swap(&mut scratch.front, &mut scratch.back);
+        }
+
+        last.propagate_into(&scratch.front[..last.inputs], out);
+    }
+
+    pub fn reset_state(&mut self) {
+        for layer in &mut self.layers {
+            layer.state.fill(0.0);
+        }
+    }
+
+    /// Compares this network against the one it was created from, on given
+    /// samples (e.g. a few recorded eye inputs).
+    ///
+    /// Both networks start from their current recurrent state, which is left
+    /// intact (see `Network::propagate_detached()`).
+    pub fn error_report(&self, original: &Network, samples: &[Vec<f32>]) -> QuantizationReport {
+        let mut quantized = self.clone();
+
+        let errors: Vec<f32> = original
+            .propagate_detached(samples)
+            .zip(samples)
+            .flat_map(|(mut trace, sample)| {
+                let expected = trace.pop().unwrap();
+                let actual = quantized.propagate(sample.clone());
+
+                expected
+                    .into_iter()
+                    .zip(actual)
+                    .map(|(expected, actual)| (expected - actual).abs())
+            })
+            .collect();
+
+        QuantizationReport {
+            scales: self.layers.iter().map(|layer| layer.scale).collect(),
+            weight_errors: self.layers.iter().map(|layer| layer.error).collect(),
+            max_output_error: errors.iter().copied().fold(0.0, f32::max),
+            mean_output_error: errors.iter().sum::<f32>() / errors.len().max(1) as f32,
+        }
+    }
+}
+
+impl<W: QuantizedWeight> QuantizedLayer<W> {
+    fn new(layer: &Layer) -> Self {
+        let max = layer
+            .weights
+            .iter()
+            .chain(&layer.recurrent)
+            .fold(0.0f32, |max, weight| max.max(weight.abs()));
+
+        let scale = if max > 0.0 { max / W::MAX } else { 1.0 };
+        let mut error = 0.0f32;
+
+        let mut quantize = |weights: &[f32]| -> Vec<W> {
+            weights
+                .iter()
+                .map(|&weight| {
+                    let quantized = W::quantize(weight / scale);
+                    error = error.max((quantized.into() * scale - weight).abs());
+                    quantized
+                })
+                .collect()
+        };
+
+        let weights = quantize(&layer.weights);
+        let recurrent = quantize(&layer.recurrent);
+
+        Self {
+            kind: layer.kind,
+            inputs: layer.inputs,
+            outputs: layer.outputs,
+            row_len: layer.weights.len() / layer.biases.len().max(1),
+            scale,
+            biases: layer.biases.clone(),
+            weights,
+            recurrent,
+            taus: layer.taus.clone(),
+            state: layer.state.clone(),
+            activation: layer.activation.clone(),
+            error,
+        }
+    }
+
+    fn propagate_into(&mut self, inputs: &[f32], outputs: &mut [f32]) {
+        let mut state = core::mem::take(&mut self.state);
+        self.step(&mut state, inputs, outputs);
+        self.state = state;
+    }
+
+    fn step(&self, state: &mut [f32], inputs: &[f32], outputs: &mut [f32]) {
+        match &self.kind {
+            LayerKind::Conv1d(conv) => {
+                for (position, outputs) in outputs.chunks_exact_mut(conv.channels).enumerate() {
+                    for (row, output) in outputs.iter_mut().enumerate() {
+                        let sum: f32 = conv
+                            .window(self.inputs, position)
+                            .zip(self.row(row))
+                            .map(|(input, &weight)| inputs[input] * weight.into())
+                            .sum();
+
+                        *output = sum * self.scale + self.biases[row];
+                    }
+                }
+            }
+
+            LayerKind::Ctrnn(ctrnn) => {
+                // All neurons have to see the same (previous) state, so the
+                // nets get computed first - into `outputs`, to avoid
+                // allocating
+                for (row, output) in outputs.iter_mut().enumerate() {
+                    let fired = state
+                        .iter()
+                        .zip(&self.biases)
+                        .map(|(state, bias)| self.activation.apply(state + bias));
+
+                    let recurrent: f32 = self
+                        .recurrent_row(row)
+                        .iter()
+                        .zip(fired)
+                        .map(|(&weight, fired)| weight.into() * fired)
+                        .sum();
+
+                    *output = (dot(self.row(row), inputs) + recurrent) * self.scale;
+                }
+
+                for (row, output) in outputs.iter_mut().enumerate() {
+                    state[row] += ctrnn.rate(self.taus[row]) * (*output - state[row]);
+                    *output = state[row] + self.biases[row];
+                }
+            }
+
+            LayerKind::Dense | LayerKind::Recurrent => {
+                for (row, output) in outputs.iter_mut().enumerate() {
+                    let sum = dot(self.row(row), inputs) + dot(self.recurrent_row(row), state);
+                    *output = sum * self.scale + self.biases[row];
+                }
+            }
+        }
+
+        for output in outputs.iter_mut() {
+            *output = self.activation.apply(*output);
+        }
+
+        if self.kind == LayerKind::Recurrent {
+            state.copy_from_slice(outputs);
+        }
+    }
+
+    fn row(&self, row: usize) -> &[W] {
+        &self.weights[row * self.row_len..][..self.row_len]
+    }
+
+    fn recurrent_row(&self, row: usize) -> &[W] {
+        match self.kind {
+            LayerKind::Recurrent | LayerKind::Ctrnn(_) => {
+                &self.recurrent[row * self.outputs..][..self.outputs]
+            }
+            LayerKind::Dense | LayerKind::Conv1d(_) => &[],
+        }
+    }
+}
+
+fn dot<W: QuantizedWeight>(weights: &[W], values: &[f32]) -> f32 {
+    weights
+        .iter()
+        .zip(values)
+        .map(|(&weight, value)| weight.into() * value)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{Conv1d, Ctrnn, Initialization, LayerTopology};
+
+    fn network() -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Network::random(
+            &mut rng,
+            &[
+                LayerTopology::new(9),
+                LayerTopology::new(18)
+                    .with_kind(LayerKind::Conv1d(Conv1d::new(3, 2).with_circular_padding())),
+                LayerTopology::new(6).with_kind(LayerKind::Recurrent),
+                LayerTopology::new(4)
+                    .with_kind(LayerKind::Ctrnn(Ctrnn::default()))
+                    .with_activation(Activation::Tanh),
+                LayerTopology::new(2).with_activation(Activation::Tanh),
+            ],
+            Initialization::default(),
+        )
+    }
+
+    fn samples() -> Vec<Vec<f32>> {
+        (0..20)
+            .map(|n| {
+                (0..9)
+                    .map(|cell| ((n * 9 + cell) as f32 * 0.37).sin().abs())
+                    .collect()
+            })
+            .collect()
+    }
+
+    mod quantize {
+        use super::*;
+
+        #[test]
+        fn i8() {
+            let network = network();
+            let report = network.quantize::<i8>().error_report(&network, &samples());
+
+            for (error, scale) in report.weight_errors.iter().zip(&report.scales) {
+                assert!(*error <= scale / 2.0 + f32::EPSILON);
+            }
+
+            assert!(report.max_output_error > 0.0);
+            assert!(report.max_output_error < 0.05, "{:?}", report);
+            assert!(report.mean_output_error <= report.max_output_error);
+        }
+
+        #[test]
+        fn i16_is_more_precise() {
+            let network = network();
+            let report_i8 = network.quantize::<i8>().error_report(&network, &samples());
+            let report_i16 = network.quantize::<i16>().error_report(&network, &samples());
+
+            assert!(report_i16.max_output_error < report_i8.max_output_error / 50.0);
+        }
+
+        #[test]
+        fn propagate_into_matches_propagate() {
+            let (mut a, mut b) = (network().quantize::<i8>(), network().quantize::<i8>());
+            let mut scratch = PropagationBuffer::default();
+
+            for sample in samples() {
+                let mut out = [0.0; 2];
+                a.propagate_into(&sample, &mut scratch, &mut out);
+
+                assert_eq!(out.to_vec(), b.propagate(sample));
+            }
+        }
+    }
+
+    mod error_report {
+        use super::*;
+
+        #[test]
+        fn preserves_state() {
+            let mut network = network();
+            let mut quantized = network.quantize::<i16>();
+
+            network.propagate(samples()[0].clone());
+            quantized.propagate(samples()[0].clone());
+
+            let state = |network: &Network, quantized: &QuantizedNetwork<i16>| {
+                (
+                    network.layers[1].state.clone(),
+                    quantized.layers[1].state.clone(),
+                )
+            };
+
+            let before = state(&network, &quantized);
+            quantized.error_report(&network, &samples());
+
+            assert_eq!(state(&network, &quantized), before);
+        }
+    }
+}
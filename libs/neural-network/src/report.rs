@@ -0,0 +1,340 @@
+//! Human-readable views of a network, for comparing evolved brains.
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{Activation, Layer, LayerKind, Network};
+
+#[derive(Clone, Debug)]
+pub struct Summary {
+    pub inputs: usize,
+    pub parameters: usize,
+    pub connections: usize,
+    pub layers: Vec<LayerSummary>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LayerSummary {
+    pub kind: LayerKind,
+    pub activation: Activation,
+    pub neurons: usize,
+    pub parameters: usize,
+    pub connections: usize,
+    /// Statistics of the active connections' weights, including recurrent
+    /// ones.
+    pub weights: WeightStats,
+    pub biases: WeightStats,
+    /// Statistics of the time constants; `None` for non-CTRNN layers.
+    pub taus: Option<WeightStats>,
+    /// Number of ReLU neurons that didn't fire for any of the samples;
+    /// `None` for other activations.
+    pub dead: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WeightStats {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub std: f32,
+}
+
+impl WeightStats {
+    fn new(weights: &[f32]) -> Self {
+        if weights.is_empty() {
+            return Self::default();
+        }
+
+        let n = weights.len() as f32;
+        let mean = weights.iter().sum::<f32>() / n;
+        let var = weights.iter().map(|w| (w - mean) * (w - mean)).sum::<f32>() / n;
+
+        Self {
+            min: weights.iter().copied().fold(f32::INFINITY, f32::min),
+            max: weights.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            mean,
+            std: num_traits::Float::sqrt(var),
+        }
+    }
+}
+
+impl Network {
+    /// Describes this network; `samples` (e.g. a few recorded eye inputs)
+    /// are used to find dead ReLUs.
+    pub fn summary(&self, samples: &[Vec<f32>]) -> Summary {
+        let mut fired: Vec<_> = self
+            .layers
+            .iter()
+            .map(|layer| vec![false; layer.outputs()])
+            .collect();
+
+        for trace in self.propagate_detached(samples) {
+            for (fired, outputs) in fired.iter_mut().zip(&trace[1..]) {
+                for (fired, &output) in fired.iter_mut().zip(outputs) {
+                    *fired |= output > 0.0;
+                }
+            }
+        }
+
+        let layers: Vec<_> = self
+            .layers
+            .iter()
+            .zip(fired)
+            .map(|(layer, fired)| LayerSummary {
+                kind: layer.kind(),
+                activation: layer.activation().clone(),
+                neurons: layer.outputs(),
+                parameters: layer.weights().count(),
+                connections: layer.connections(),
+                weights: WeightStats::new(&connection_weights(layer)),
+                biases: WeightStats::new(&layer.biases),
+                taus: matches!(layer.kind(), LayerKind::Ctrnn(_))
+                    .then(|| WeightStats::new(&layer.taus)),
+                dead: matches!(layer.activation(), Activation::ReLU)
+                    .then(|| fired.iter().filter(|&&fired| !fired).count()),
+            })
+            .collect();
+
+        Summary {
+            inputs: self.input_size(),
+            parameters: layers.iter().map(|layer| layer.parameters).sum(),
+            connections: layers.iter().map(|layer| layer.connections).sum(),
+            layers,
+        }
+    }
+
+    /// Renders this network as a Graphviz graph, with one node per neuron.
+    ///
+    /// Positive weights are drawn blue and negative ones red, with the
+    /// thickness of each edge proportional to its weight's magnitude.
+    pub fn to_dot(&self) -> String {
+        let max = self
+            .layers
+            .iter()
+            .flat_map(connection_weights)
+            .map(f32::abs)
+            .fold(0.0, f32::max)
+            .max(f32::EPSILON);
+
+        let edge = |dot: &mut String, from: String, to: String, weight: f32| {
+            let color = if weight >= 0.0 { "blue" } else { "red" };
+            let width = 0.25 + 2.75 * weight.abs() / max;
+
+            writeln!(
+                dot,
+                "    {} -> {} [color={}, penwidth={:.2}, tooltip=\"{:.3}\"];",
+                from, to, color, width, weight
+            )
+            .unwrap();
+        };
+
+        let node = |layer: usize, neuron: usize| format!("n{}_{}", layer, neuron);
+        let mut dot =
+            String::from("digraph network {\n    rankdir=LR;\n    node [shape=circle];\n");
+
+        for neuron in 0..self.input_size() {
+            writeln!(dot, "    {} [label=\"in {}\"];", node(0, neuron), neuron).unwrap();
+        }
+
+        for (idx, layer) in self.layers.iter().enumerate() {
+            for neuron in 0..layer.outputs() {
+                let (id, bias) = (node(idx + 1, neuron), layer.bias(neuron));
+                writeln!(dot, "    {} [label=\"{:.2}\"];", id, bias).unwrap();
+            }
+        }
+
+        for (idx, layer) in self.layers.iter().enumerate() {
+            for (from, to, weight) in layer.edges() {
+                edge(&mut dot, node(idx, from), node(idx + 1, to), weight);
+            }
+
+            for (from, to, weight) in layer.recurrent_edges() {
+                edge(&mut dot, node(idx + 1, from), node(idx + 1, to), weight);
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns weights of the layer's active connections - i.e. without biases
+/// and time constants, which follow different distributions.
+fn connection_weights(layer: &Layer) -> Vec<f32> {
+    let inputs = layer
+        .weights
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| layer.mask.as_ref().is_none_or(|mask| mask[*idx]))
+        .map(|(_, &weight)| weight);
+
+    inputs.chain(layer.recurrent.iter().copied()).collect()
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} inputs, {} parameters, {} connections",
+            self.inputs, self.parameters, self.connections
+        )?;
+
+        for (idx, layer) in self.layers.iter().enumerate() {
+            write!(
+                f,
+                "#{} {:?} {:?} x{}: {} params, w = [{:.3}, {:.3}], mean {:.3}, std {:.3}",
+                idx + 1,
+                layer.kind,
+                layer.activation,
+                layer.neurons,
+                layer.parameters,
+                layer.weights.min,
+                layer.weights.max,
+                layer.weights.mean,
+                layer.weights.std,
+            )?;
+
+            write!(
+                f,
+                ", b = [{:.3}, {:.3}]",
+                layer.biases.min, layer.biases.max
+            )?;
+
+            if let Some(taus) = layer.taus {
+                write!(f, ", tau = [{:.3}, {:.3}]", taus.min, taus.max)?;
+            }
+
+            if let Some(dead) = layer.dead {
+                write!(f, ", {} dead", dead)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::{Ctrnn, LayerTopology};
+
+    fn network() -> Network {
+        let layers = &[
+            LayerTopology::new(2),
+            LayerTopology::new(2),
+            LayerTopology::new(1).with_activation(Activation::Tanh),
+        ];
+
+        // The second hidden neuron only ever sees negative inputs
+        Network::from_weights(
+            layers,
+            vec![0.1, 0.5, 0.5, -1.0, -0.5, -0.5, 0.0, 1.0, -2.0],
+        )
+    }
+
+    fn ctrnn() -> Network {
+        let layers = &[
+            LayerTopology::new(1),
+            LayerTopology::new(2).with_kind(LayerKind::Ctrnn(Ctrnn::new(0.5))),
+        ];
+
+        // Each row is: bias, tau, input weight, recurrent weights
+        Network::from_weights(
+            layers,
+            vec![0.2, 10.0, 0.5, 0.0, 0.25, -0.1, 0.5, -0.5, 0.1, 0.0],
+        )
+    }
+
+    mod summary {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let summary = network().summary(&[vec![0.2, 0.4], vec![1.0, 0.0]]);
+
+            assert_eq!(summary.inputs, 2);
+            assert_eq!(summary.parameters, 9);
+            assert_eq!(summary.connections, 6);
+            assert_eq!(summary.layers.len(), 2);
+
+            let hidden = &summary.layers[0];
+
+            assert_eq!(hidden.parameters, 6);
+            assert_eq!(hidden.dead, Some(1));
+            assert_relative_eq!(hidden.weights.min, -0.5);
+            assert_relative_eq!(hidden.weights.max, 0.5);
+            assert_relative_eq!(hidden.weights.mean, 0.0);
+            assert_relative_eq!(hidden.weights.std, 0.5);
+            assert_relative_eq!(hidden.biases.min, -1.0);
+            assert_relative_eq!(hidden.biases.max, 0.1);
+            assert_relative_eq!(hidden.biases.mean, -0.45);
+            assert_eq!(hidden.taus, None);
+
+            assert_eq!(summary.layers[1].dead, None);
+        }
+
+        #[test]
+        fn display() {
+            let summary = network().summary(&[vec![0.2, 0.4]]).to_string();
+
+            assert!(summary.starts_with("2 inputs, 9 parameters, 6 connections\n"));
+            assert!(summary.contains("#1 Dense ReLU x2: 6 params"));
+            assert!(summary.contains(", 1 dead\n"));
+        }
+
+        #[test]
+        fn reports_taus_separately() {
+            let summary = ctrnn().summary(&[]);
+            let layer = &summary.layers[0];
+
+            assert_relative_eq!(layer.weights.min, -0.5);
+            assert_relative_eq!(layer.weights.max, 0.5);
+            assert_relative_eq!(layer.biases.max, 0.2);
+            assert_relative_eq!(layer.taus.unwrap().min, 0.5);
+            assert_relative_eq!(layer.taus.unwrap().max, 10.0);
+
+            assert!(summary.to_string().contains(", tau = [0.500, 10.000]"));
+        }
+
+        #[test]
+        fn without_samples_all_relus_are_dead() {
+            assert_eq!(network().summary(&[]).layers[0].dead, Some(2));
+        }
+    }
+
+    mod to_dot {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let dot = network().to_dot();
+
+            assert!(dot.starts_with("digraph network {"));
+            assert!(dot.contains("n0_1 [label=\"in 1\"];"));
+            assert!(dot.contains("n1_1 [label=\"-1.00\"];"));
+            assert!(dot.contains("n0_0 -> n1_0 [color=blue, penwidth=0.94"));
+            assert!(dot.contains("n1_1 -> n2_0 [color=red, penwidth=3.00"));
+            assert_eq!(dot.matches("->").count(), 6);
+        }
+
+        #[test]
+        fn ignores_taus() {
+            let dot = ctrnn().to_dot();
+
+            // The widest edge is the strongest connection, not the 10.0 tau
+            assert!(dot.contains("n0_0 -> n1_0 [color=blue, penwidth=3.00"));
+        }
+
+        #[test]
+        fn skips_pruned_connections() {
+            let mut network = network();
+            network.prune(0.75);
+
+            assert_eq!(network.to_dot().matches("->").count(), 2);
+        }
+    }
+}
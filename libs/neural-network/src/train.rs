@@ -0,0 +1,459 @@
+//! Supervised training through backpropagation.
+//!
+//! Meant for pre-training brains on hand-made datasets before handing them
+//! over to the genetic algorithm - since training updates the very same
+//! `Network`, its `weights()` can seed a population directly.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{Float, Network};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample<F = f32> {
+    pub inputs: Vec<F>,
+    pub targets: Vec<F>,
+}
+
+impl Sample {
+    pub fn new(inputs: Vec<f32>, targets: Vec<f32>) -> Self {
+        Self { inputs, targets }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Optimizer {
+    Sgd {
+        learning_rate: f32,
+        momentum: f32,
+    },
+    Adam {
+        learning_rate: f32,
+        beta1: f32,
+        beta2: f32,
+        epsilon: f32,
+    },
+}
+
+impl Optimizer {
+    pub fn sgd(learning_rate: f32) -> Self {
+        Self::Sgd {
+            learning_rate,
+            momentum: 0.0,
+        }
+    }
+
+    pub fn adam(learning_rate: f32) -> Self {
+        Self::Adam {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+}
+
+pub struct Trainer<F = f32> {
+    optimizer: Optimizer,
+    // First & second moment estimates, in the `weights()` order
+    m: Vec<F>,
+    v: Vec<F>,
+    steps: i32,
+}
+
+impl<F: Float> Trainer<F> {
+    pub fn new(optimizer: Optimizer) -> Self {
+        Self {
+            optimizer,
+            m: Vec::new(),
+            v: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// Runs `epochs` passes over `samples`, updating the network after each
+    /// `batch_size` samples; returns the mean loss of the last epoch.
+    pub fn train(
+        &mut self,
+        network: &mut Network<F>,
+        samples: &[Sample<F>],
+        epochs: usize,
+        batch_size: usize,
+    ) -> F {
+        assert!(batch_size > 0);
+
+        let mut loss = F::zero();
+
+        for _ in 0..epochs {
+            loss = samples
+                .chunks(batch_size)
+                .map(|batch| self.step(network, batch) * count(batch.len()))
+                .sum::<F>()
+                / count(samples.len());
+        }
+
+        loss
+    }
+
+    /// Performs a single update over the mean gradient of `batch`; returns
+    /// the batch's mean loss (before the update).
+    pub fn step(&mut self, network: &mut Network<F>, batch: &[Sample<F>]) -> F {
+        let mut loss = F::zero();
+        let mut grad = vec![F::zero(); network.weights().count()];
+
+        for sample in batch {
+            let (sample_loss, sample_grad) = network.gradient(&sample.inputs, &sample.targets);
+
+            loss += sample_loss;
+
+            for (grad, sample_grad) in grad.iter_mut().zip(sample_grad) {
+                *grad += sample_grad;
+            }
+        }
+
+        let n = count(batch.len().max(1));
+
+        for grad in &mut grad {
+            *grad /= n;
+        }
+
+        self.apply(network, &grad);
+
+        loss / n
+    }
+
+    fn apply(&mut self, network: &mut Network<F>, grad: &[F]) {
+        if self.m.len() != grad.len() {
+            self.m = vec![F::zero(); grad.len()];
+            self.v = vec![F::zero(); grad.len()];
+            self.steps = 0;
+        }
+
+        self.steps += 1;
+
+        let mut idx = 0;
+
+        match self.optimizer {
+            Optimizer::Sgd {
+                learning_rate,
+                momentum,
+            } => {
+                let (learning_rate, momentum) = (F::from_f32(learning_rate), F::from_f32(momentum));
+
+                network.for_each_weight_mut(|weight| {
+                    let velocity = &mut self.m[idx];
+
+                    *velocity = momentum * *velocity - learning_rate * grad[idx];
+                    *weight += *velocity;
+
+                    idx += 1;
+                });
+            }
+
+            Optimizer::Adam {
+                learning_rate,
+                beta1,
+                beta2,
+                epsilon,
+            } => {
+                let [learning_rate, beta1, beta2, epsilon] =
+                    [learning_rate, beta1, beta2, epsilon].map(F::from_f32);
+
+                let m_correction = F::one() - beta1.powi(self.steps);
+                let v_correction = F::one() - beta2.powi(self.steps);
+
+                network.for_each_weight_mut(|weight| {
+                    let (m, v, g) = (&mut self.m[idx], &mut self.v[idx], grad[idx]);
+
+                    *m = beta1 * *m + (F::one() - beta1) * g;
+                    *v = beta2 * *v + (F::one() - beta2) * g * g;
+
+                    let m_hat = *m / m_correction;
+                    let v_hat = *v / v_correction;
+
+                    *weight -= learning_rate * m_hat / (v_hat.sqrt() + epsilon);
+
+                    idx += 1;
+                });
+            }
+        }
+    }
+}
+
+impl<F: Float> Network<F> {
+    /// Returns the mean squared error of the network's outputs for given
+    /// sample, and its gradient with respect to all weights (in the
+    /// `weights()` order).
+    ///
+    /// Recurrent layers use (but don't update) their current state, which
+    /// is treated as a constant - that is, there's no backpropagation
+    /// through time.
+    pub fn gradient(&self, inputs: &[F], targets: &[F]) -> (F, Vec<F>) {
+        assert_eq!(targets.len(), self.output_size());
+
+        let mut activations = vec![inputs.to_vec()];
+        let mut pre_activations = Vec::with_capacity(self.layers.len());
+
+        for layer in &self.layers {
+            let mut pre = vec![F::zero(); layer.outputs()];
+            layer.forward(&layer.state, activations.last().unwrap(), &mut pre);
+
+            let outputs = pre.iter().map(|&x| layer.activation.apply(x)).collect();
+
+            pre_activations.push(pre);
+            activations.push(outputs);
+        }
+
+        let outputs = activations.last().unwrap();
+        let n = count(outputs.len());
+
+        let loss = outputs
+            .iter()
+            .zip(targets)
+            .map(|(&y, &t)| (y - t).powi(2))
+            .sum::<F>()
+            / n;
+
+        let mut grad_outputs: Vec<_> = outputs
+            .iter()
+            .zip(targets)
+            .map(|(&y, &t)| F::from_f32(2.0) * (y - t) / n)
+            .collect();
+
+        let sizes: Vec<_> = self.layers.iter().map(|l| l.weights().count()).collect();
+        let mut grad = vec![F::zero(); sizes.iter().sum()];
+        let mut end = grad.len();
+
+        for (idx, layer) in self.layers.iter().enumerate().rev() {
+            let start = end - sizes[idx];
+            let mut grad_inputs = vec![F::zero(); layer.inputs()];
+
+            layer.backward(
+                &activations[idx],
+                &pre_activations[idx],
+                &activations[idx + 1],
+                &grad_outputs,
+                &mut grad[start..end],
+                &mut grad_inputs,
+            );
+
+            grad_outputs = grad_inputs;
+            end = start;
+        }
+
+        (loss, grad)
+    }
+
+    pub(crate) fn for_each_weight_mut(&mut self, mut f: impl FnMut(&mut F)) {
+        for layer in &mut self.layers {
+            layer.for_each_weight_mut(&mut f);
+        }
+    }
+}
+
+fn count<F: Float>(n: usize) -> F {
+    F::from(n).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{Activation, Initialization, LayerKind, LayerTopology};
+
+    fn network(rng: &mut ChaCha8Rng) -> Network {
+        let layers = &[
+            LayerTopology::new(3),
+            LayerTopology::new(4)
+                .with_kind(LayerKind::Recurrent)
+                .with_activation(Activation::Tanh),
+            LayerTopology::new(2).with_activation(Activation::Sigmoid),
+        ];
+
+        Network::random(rng, layers, Initialization::default())
+    }
+
+    mod gradient {
+        use super::*;
+
+        use crate::{Conv1d, Ctrnn};
+
+        // `prepare` gets called on every network before it's evaluated, so
+        // that stateful layers can be put into a known state
+        fn check(
+            mut network: Network,
+            inputs: &[f32],
+            targets: &[f32],
+            prepare: impl Fn(&mut Network),
+        ) {
+            const H: f32 = 1e-2;
+
+            prepare(&mut network);
+
+            let (_, actual) = network.gradient(inputs, targets);
+            let topology = network.topology();
+            let weights: Vec<_> = network.weights().collect();
+
+            for idx in 0..weights.len() {
+                let loss = |delta: f32| {
+                    let mut weights = weights.clone();
+                    weights[idx] += delta;
+
+                    let mut network: Network = Network::from_weights(&topology, weights);
+                    prepare(&mut network);
+                    network.gradient(inputs, targets).0
+                };
+
+                let expected = (loss(H) - loss(-H)) / (2.0 * H);
+
+                approx::assert_relative_eq!(actual[idx], expected, epsilon = 1e-3);
+            }
+        }
+
+        #[test]
+        fn matches_finite_differences() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Gives the recurrent layer a non-zero state to differentiate
+            // against (without making the state depend on the weights)
+            check(
+                network(&mut rng),
+                &[0.5, -0.3, 0.8],
+                &[1.0, 0.0],
+                |network| {
+                    network.layers[0]
+                        .state
+                        .copy_from_slice(&[0.4, -0.2, 0.9, 0.1]);
+                },
+            );
+        }
+
+        #[test]
+        fn conv1d() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let conv = Conv1d::new(3, 2).with_circular_padding();
+
+            let network: Network = Network::random(
+                &mut rng,
+                &[
+                    LayerTopology::new(5),
+                    LayerTopology::new(10)
+                        .with_kind(LayerKind::Conv1d(conv))
+                        .with_activation(Activation::Tanh),
+                    LayerTopology::new(2).with_activation(Activation::Sigmoid),
+                ],
+                Initialization::default(),
+            );
+
+            check(network, &[0.5, -0.3, 0.8, 0.1, -0.9], &[1.0, 0.0], |_| ());
+        }
+
+        #[test]
+        fn ctrnn() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let network: Network = Network::random(
+                &mut rng,
+                &[
+                    LayerTopology::new(3),
+                    LayerTopology::new(3)
+                        .with_kind(LayerKind::Ctrnn(Ctrnn::new(0.1)))
+                        .with_activation(Activation::Tanh),
+                    LayerTopology::new(2).with_activation(Activation::Sigmoid),
+                ],
+                Initialization::default(),
+            );
+
+            check(network, &[0.5, -0.3, 0.8], &[1.0, 0.0], |network| {
+                network.layers[0].state.copy_from_slice(&[0.4, -0.7, 0.2]);
+            });
+        }
+
+        #[test]
+        fn f64() {
+            const H: f64 = 1e-6;
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let topology = [
+                LayerTopology::new(3),
+                LayerTopology::new(3)
+                    .with_kind(LayerKind::Ctrnn(Ctrnn::new(0.1)))
+                    .with_activation(Activation::Tanh),
+                LayerTopology::new(4)
+                    .with_kind(LayerKind::Recurrent)
+                    .with_activation(Activation::Tanh),
+                LayerTopology::new(2).with_activation(Activation::Sigmoid),
+            ];
+
+            let network = Network::<f64>::random(&mut rng, &topology, Initialization::default());
+            let (inputs, targets) = ([0.5, -0.3, 0.8], [1.0, 0.0]);
+            let (_, actual) = network.gradient(&inputs, &targets);
+            let weights: Vec<_> = network.weights().collect();
+
+            for idx in 0..weights.len() {
+                let loss = |delta: f64| {
+                    let mut weights = weights.clone();
+                    weights[idx] += delta;
+
+                    Network::<f64>::from_weights(&topology, weights)
+                        .gradient(&inputs, &targets)
+                        .0
+                };
+
+                let expected = (loss(H) - loss(-H)) / (2.0 * H);
+
+                // Much tighter than what `f32` can get to
+                approx::assert_relative_eq!(actual[idx], expected, epsilon = 1e-8);
+            }
+        }
+    }
+
+    mod trainer {
+        use super::*;
+
+        // "Turn toward the brightest eye cell": output is positive when the
+        // left cell is brighter, and negative otherwise.
+        fn samples() -> Vec<Sample> {
+            [[0.9, 0.1], [0.2, 0.7], [0.6, 0.0], [0.1, 0.5], [1.0, 0.4]]
+                .into_iter()
+                .map(|[l, r]| Sample::new(vec![l, r], vec![(l - r).signum() * 0.5]))
+                .collect()
+        }
+
+        fn train(optimizer: Optimizer) -> (f32, f32) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut network: Network = Network::random(
+                &mut rng,
+                &[
+                    LayerTopology::new(2),
+                    LayerTopology::new(4).with_activation(Activation::Tanh),
+                    LayerTopology::new(1).with_activation(Activation::Tanh),
+                ],
+                Initialization::default(),
+            );
+
+            let mut trainer = Trainer::new(optimizer);
+            let before = trainer.train(&mut network, &samples(), 1, 5);
+            let after = trainer.train(&mut network, &samples(), 200, 5);
+
+            (before, after)
+        }
+
+        #[test]
+        fn sgd() {
+            let (before, after) = train(Optimizer::sgd(0.5));
+
+            assert!(after < before / 10.0, "{} -> {}", before, after);
+        }
+
+        #[test]
+        fn adam() {
+            let (before, after) = train(Optimizer::adam(0.05));
+
+            assert!(after < before / 10.0, "{} -> {}", before, after);
+        }
+    }
+}
@@ -1,53 +1,103 @@
 use lib_genetic_algorithm::{
-    crossover::uniform::UniformCrossover, mutation::gaussian::GaussianMutation,
-    select::roulette_wheel::RouletteWheelSelection, GeneticAlgorithm,
+    crossover::uniform::UniformCrossover,
+    individual::Individual,
+    mutation::uniform::UniformMutation,
+    select::{roulette_wheel::RouletteWheelSelection, SelectionMethod},
+    statistics::Statistics,
+    GeneticAlgorithm,
+};
+use lib_neural_network::{
+    neat::{Genome, InnovationTracker},
+    PropagationBuffer,
 };
 use nalgebra::{distance, wrap, Rotation2, Vector2};
 use rand::{Rng, RngCore};
-use world::individual::AnimalIndividual;
-pub use world::World;
+use world::{animal::eyes::Eyes, individual::AnimalIndividual};
+pub use world::{BrainKind, World};
 
 pub mod world;
 
-use std::f32::consts::FRAC_PI_2;
+use std::{collections::VecDeque, f32::consts::FRAC_PI_2};
 
 const SPEED_MIN: f32 = 0.0006;
 const SPEED_MAX: f32 = 0.0012;
 const SPEED_ACCEL: f32 = 0.2;
 const ROTATION_ACCEL: f32 = FRAC_PI_2;
 const GENERATION_LENGTH: usize = 2500;
+const HISTORY_LENGTH: usize = 1000;
+
+// Chance and magnitude of weight mutations, shared by all brain kinds.
+//
+// Chosen with a bit of experimentation.
+//
+// Higher values can make the simulation more chaotic, which - a bit
+// counterintuitively - might allow for it to discover *better* solutions;
+// but the trade-off is that higher values might also cause current, good
+// enough solutions to be discarded.
+const MUTATION_CHANCE: f32 = 0.01;
+const MUTATION_COEFF: f32 = 0.3;
+
+// Chances of NEAT's structural mutations, per child
+const ADD_NODE_CHANCE: f64 = 0.03;
+const ADD_CONNECTION_CHANCE: f64 = 0.05;
 
 pub struct Simulation {
     pub world: World,
     ga: GeneticAlgorithm<RouletteWheelSelection>,
     age: usize,
+    brain: BrainKind,
+    // Numbers NEAT brains' genes, so that they can be crossed over
+    tracker: InnovationTracker,
+    // Shared by all brains, since they all have the same shape
+    brain_buffer: PropagationBuffer,
+    // Ditto, for what the eyes see
+    vision_buffer: Vec<f32>,
+    // Statistics of all generations so far, oldest first
+    history: VecDeque<Statistics>,
 }
 
 impl Simulation {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
+        Self::random_with_brain(rng, BrainKind::default())
+    }
+
+    pub fn random_with_brain(rng: &mut dyn RngCore, brain: BrainKind) -> Self {
+        let mut tracker = InnovationTracker::new(Eyes::default().cells(), 2);
+        let world = World::random(rng, &mut tracker, brain);
 
         let ga = GeneticAlgorithm::new(
             RouletteWheelSelection::new(),
             UniformCrossover::new(),
-            GaussianMutation::new(0.01, 0.3),
-            // ---------------------- ^--^ -^-^
-            // | Chosen with a bit of experimentation.
-            // |
-            // | Higher values can make the simulation more chaotic,
-            // | which - a bit counterintuitively - might allow for
-            // | it to discover *better* solutions; but the trade-off
-            // | is that higher values might also cause current, good
-            // | enough solutions to be discarded.
-            // ---
+            UniformMutation::new(MUTATION_CHANCE, MUTATION_COEFF),
         );
-        Self { world, ga, age: 0 }
+        Self {
+            world,
+            ga,
+            age: 0,
+            brain,
+            tracker,
+            brain_buffer: PropagationBuffer::default(),
+            vision_buffer: Vec::new(),
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+        }
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
+    /// Returns statistics of the most recently finished generation; `None`
+    /// until the first one finishes.
+    pub fn statistics(&self) -> Option<&Statistics> {
+        self.history.back()
+    }
+
+    /// Returns statistics of recently finished generations (up to the last
+    /// thousand of them), oldest first.
+    pub fn history(&self) -> &VecDeque<Statistics> {
+        &self.history
+    }
+
     pub fn step(&mut self, rng: &mut dyn RngCore) {
         self.process_collisions(rng);
         self.process_brains();
@@ -83,12 +133,20 @@ impl Simulation {
 
     fn process_brains(&mut self) {
         for animal in &mut self.world.animals {
-            let vision =
-                animal
-                    .eyes
-                    .process_vision(animal.position, animal.rotation, &self.world.foods);
+            self.vision_buffer.resize(animal.eyes.cells(), 0.0);
+
+            animal.eyes.process_vision(
+                animal.position,
+                animal.rotation,
+                &self.world.foods,
+                &mut self.vision_buffer,
+            );
+
+            let mut response = [0.0; 2];
 
-            let response = animal.brain.nn.propagate(vision);
+            animal
+                .brain
+                .propagate_into(&self.vision_buffer, &mut self.brain_buffer, &mut response);
 
             // ---
             // | Limits number to given range.
@@ -132,22 +190,75 @@ impl Simulation {
             .collect();
 
         // Evolves this `Vec<AnimalIndividual>`
-        let evolved_population = self.ga.evolve(&current_population, rng);
+        let (evolved_population, statistics) = match self.brain {
+            BrainKind::Neat => self.evolve_neat(&current_population, rng),
+            _ => self.ga.evolve(&current_population, rng),
+        };
+
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(statistics);
 
         // Transforms `Vec<AnimalIndividual>` back into `Vec<Animal>`
         self.world.animals = evolved_population
             .into_iter()
-            .map(|individual| individual.into_animal(rng))
+            .map(|individual| individual.into_animal(self.brain, rng))
             .collect();
 
         for food in &mut self.world.foods {
             food.position = rng.gen();
         }
     }
+
+    /// NEAT counterpart of `GeneticAlgorithm::evolve()` - genomes don't fit
+    /// into fixed-length chromosomes, so they get bred here.
+    fn evolve_neat(
+        &mut self,
+        population: &[AnimalIndividual],
+        rng: &mut dyn RngCore,
+    ) -> (Vec<AnimalIndividual>, Statistics) {
+        let selection = RouletteWheelSelection::new();
+
+        let offspring = (0..population.len())
+            .map(|_| {
+                let parent_a = selection.select(population, rng);
+                let parent_b = selection.select(population, rng);
+
+                let (fitter, other) = if parent_a.fitness() >= parent_b.fitness() {
+                    (parent_a, parent_b)
+                } else {
+                    (parent_b, parent_a)
+                };
+
+                let mut genome = Genome::crossover(
+                    fitter.genome().expect("NEAT animal has no genome"),
+                    other.genome().expect("NEAT animal has no genome"),
+                    rng,
+                );
+
+                genome.mutate_weights(rng, MUTATION_CHANCE, MUTATION_COEFF);
+
+                if rng.gen_bool(ADD_NODE_CHANCE) {
+                    genome.mutate_add_node(rng, &mut self.tracker);
+                }
+
+                if rng.gen_bool(ADD_CONNECTION_CHANCE) {
+                    genome.mutate_add_connection(rng, &mut self.tracker);
+                }
+
+                AnimalIndividual::from_genome(genome)
+            })
+            .collect();
+
+        (offspring, Statistics::new(population))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use approx::assert_relative_eq;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
@@ -161,4 +272,103 @@ mod tests {
         assert_eq!(sim.world().animals().len(), 40);
         assert_eq!(sim.world().food().len(), 60);
     }
+
+    #[test]
+    fn ctrnn_brains_survive_evolution() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random_with_brain(&mut rng, BrainKind::Ctrnn);
+
+        // Roulette wheel needs someone to have eaten something
+        sim.world.animals[0].satiation = 1;
+        sim.evolve(&mut rng);
+
+        let chromosome = sim.world().animals()[0].as_chromosome();
+        assert_eq!(chromosome.len(), 9 * 18 + 18 + 2 * (2 + 18 + 2));
+    }
+
+    #[test]
+    fn neat_brains_evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random_with_brain(&mut rng, BrainKind::Neat);
+
+        let nodes = |sim: &Simulation| {
+            sim.world()
+                .animals()
+                .iter()
+                .map(|animal| animal.brain().genome().unwrap().nodes().len())
+                .max()
+                .unwrap()
+        };
+
+        assert_eq!(nodes(&sim), 9 + 2);
+
+        for _ in 0..10 {
+            // Roulette wheel needs someone to have eaten something
+            sim.world.animals[0].satiation = 1;
+            sim.evolve(&mut rng);
+        }
+
+        assert_eq!(sim.history().len(), 10);
+        assert!(nodes(&sim) > 9 + 2);
+
+        // ... and the evolved brains still work
+        sim.step(&mut rng);
+    }
+
+    #[test]
+    fn seeded_simulation_is_reproducible() {
+        let chromosomes = || {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let sim = Simulation::random(&mut rng);
+
+            sim.world()
+                .animals()
+                .iter()
+                .map(|animal| animal.as_chromosome())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(chromosomes(), chromosomes());
+    }
+
+    #[test]
+    fn statistics() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        assert!(sim.statistics().is_none());
+
+        sim.world.animals[0].satiation = 3;
+        sim.world.animals[1].satiation = 1;
+        sim.evolve(&mut rng);
+
+        let statistics = sim.statistics().unwrap();
+
+        assert_eq!(statistics.max_fitness(), 3.0);
+        assert_eq!(statistics.median_fitness(), 0.0);
+        assert_relative_eq!(statistics.mean_fitness(), 0.1);
+        assert_eq!(statistics.zero_fitness(), 38);
+        assert!(statistics.gene_diversity() > 0.0);
+
+        sim.world.animals[0].satiation = 1;
+        sim.evolve(&mut rng);
+
+        assert_eq!(sim.history().len(), 2);
+        assert_eq!(sim.history()[0].max_fitness(), 3.0);
+        assert_eq!(sim.statistics().unwrap().max_fitness(), 1.0);
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        for generation in 1..=HISTORY_LENGTH + 5 {
+            sim.world.animals[0].satiation = generation;
+            sim.evolve(&mut rng);
+        }
+
+        assert_eq!(sim.history().len(), HISTORY_LENGTH);
+        assert_eq!(sim.history()[0].max_fitness(), 6.0);
+    }
 }
@@ -1,39 +1,280 @@
 use lib_genetic_algorithm::chromosome::Chromosome;
-use lib_neural_network::{LayerTopology, Network};
+use lib_neural_network::{
+    neat::{Genome, InnovationTracker, NeatNetwork},
+    Activation, Ctrnn, Initialization, LayerKind, LayerTopology, Network, PropagationBuffer,
+    QuantizedNetwork,
+};
 use rand::RngCore;
 
 use super::eyes::Eyes;
 
+/// Shape of brains.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BrainKind {
+    /// Eye cells -> ReLU hidden layer -> speed & rotation.
+    #[default]
+    Feedforward,
+
+    /// Like `Feedforward`, but speed & rotation come out of continuous-time
+    /// neurons, which makes them change smoothly (and lets them oscillate).
+    Ctrnn,
+
+    /// Eye cells directly connected to speed & rotation, with hidden
+    /// neurons and connections added as the brains evolve.
+    Neat,
+}
+
 pub struct Brain {
-    pub(crate) nn: Network,
+    pub(crate) nn: BrainNetwork,
+    // Chromosome's strategy parameters (e.g. self-adaptive mutation's step
+    // sizes) - they don't affect the brain, but have to survive until the
+    // next generation
+    strategy: Vec<f32>,
+}
+
+pub enum BrainNetwork {
+    /// Fixed topology (see `Brain::topology()`), evolved through the
+    /// chromosome.
+    Fixed(Network),
+
+    /// NEAT network, whose topology evolves too; it keeps its genome
+    /// around, since that's what NEAT mutations and crossover work on.
+    Neat {
+        genome: Box<Genome>,
+        network: NeatNetwork,
+    },
 }
 
 impl Brain {
-    pub fn random(_rng: &mut dyn RngCore, eye: &Eyes) -> Self {
+    /// `tracker` is used only by NEAT brains.
+    pub fn random(
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+        eye: &Eyes,
+        kind: BrainKind,
+    ) -> Self {
+        if kind == BrainKind::Neat {
+            return Self::random_neat(rng, tracker, eye);
+        }
+
         Self {
-            nn: Network::random(&Self::topology(eye)),
+            nn: BrainNetwork::Fixed(Network::random(
+                rng,
+                &Self::topology(eye, kind),
+                Initialization::default(),
+            )),
+            strategy: Vec::new(),
         }
     }
 
+    pub fn random_neat(rng: &mut dyn RngCore, tracker: &mut InnovationTracker, eye: &Eyes) -> Self {
+        let genome = Genome::minimal(rng, tracker, eye.cells(), 2)
+            .with_activations(Activation::ReLU, Activation::Tanh);
+
+        Self::from_genome(genome)
+    }
+
+    pub fn from_genome(genome: Genome) -> Self {
+        let network = genome.network();
+
+        Self {
+            nn: BrainNetwork::Neat {
+                genome: Box::new(genome),
+                network,
+            },
+            strategy: Vec::new(),
+        }
+    }
+
+    pub fn genome(&self) -> Option<&Genome> {
+        match &self.nn {
+            BrainNetwork::Fixed(_) => None,
+            BrainNetwork::Neat { genome, .. } => Some(genome),
+        }
+    }
+
+    pub(crate) fn propagate_into(
+        &mut self,
+        vision: &[f32],
+        scratch: &mut PropagationBuffer,
+        out: &mut [f32],
+    ) {
+        match &mut self.nn {
+            BrainNetwork::Fixed(nn) => nn.propagate_into(vision, scratch, out),
+            BrainNetwork::Neat { network, .. } => network.propagate_into(vision, scratch, out),
+        }
+    }
+
+    /// Returns activations of all the brain's layers for given vision,
+    /// from the eye cells up to the speed & rotation outputs.
+    ///
+    /// NEAT brains have no layers, so all their hidden nodes are reported
+    /// as a single one.
+    pub fn propagate_traced(&mut self, vision: Vec<f32>) -> Vec<Vec<f32>> {
+        match &mut self.nn {
+            BrainNetwork::Fixed(nn) => nn.propagate_traced(vision),
+            BrainNetwork::Neat { network, .. } => network.propagate_traced(vision),
+        }
+    }
+
+    /// Converts a fixed brain into a lighter, inference-only network that
+    /// takes the same vision; `None` for NEAT brains.
+    pub fn quantize(&self) -> Option<QuantizedNetwork> {
+        match &self.nn {
+            BrainNetwork::Fixed(nn) => Some(nn.quantize()),
+            BrainNetwork::Neat { .. } => None,
+        }
+    }
+
+    /// For NEAT brains this contains only the weights and biases - their
+    /// topology lives in the genome, which the fixed-length chromosome
+    /// cannot carry; `from_chromosome()` always creates a fixed brain.
     pub(crate) fn as_chromosome(&self) -> Chromosome {
-        self.nn.weights().collect()
+        let genes = match &self.nn {
+            BrainNetwork::Fixed(nn) => nn.weights().collect(),
+            BrainNetwork::Neat { genome, .. } => genome.weights().collect(),
+        };
+
+        Chromosome::new(genes).with_strategy(self.strategy.clone())
     }
 
-    fn topology(eye: &Eyes) -> [LayerTopology; 3] {
+    fn topology(eye: &Eyes, kind: BrainKind) -> [LayerTopology; 3] {
+        let output = match kind {
+            BrainKind::Feedforward => LayerKind::Dense,
+            BrainKind::Ctrnn => LayerKind::Ctrnn(Ctrnn::default()),
+            BrainKind::Neat => unreachable!("NEAT brains have no fixed topology"),
+        };
+
         [
-            LayerTopology {
-                neurons: eye.cells(),
-            },
-            LayerTopology {
-                neurons: 2 * eye.cells(),
-            },
-            LayerTopology { neurons: 2 },
+            LayerTopology::new(eye.cells()),
+            LayerTopology::new(2 * eye.cells()),
+            // Speed and rotation are relative, so the brain has to be able
+            // to slow down and to turn either way:
+            LayerTopology::new(2)
+                .with_kind(output)
+                .with_activation(Activation::Tanh),
         ]
     }
 
-    pub(crate) fn from_chromosome(chromosome: Chromosome, eye: &Eyes) -> Self {
+    pub(crate) fn from_chromosome(chromosome: Chromosome, eye: &Eyes, kind: BrainKind) -> Self {
+        let strategy = chromosome.strategy().to_vec();
+
         Self {
-            nn: Network::from_weights(&Self::topology(eye), chromosome),
+            nn: BrainNetwork::Fixed(Network::from_weights(
+                &Self::topology(eye, kind),
+                chromosome,
+            )),
+            strategy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point2, Rotation2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::world::Food;
+
+    fn tracker(eyes: &Eyes) -> InnovationTracker {
+        InnovationTracker::new(eyes.cells(), 2)
+    }
+
+    #[test]
+    fn neat_brain() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eyes = Eyes::default();
+        let mut tracker = InnovationTracker::new(eyes.cells(), 2);
+
+        let mut genome = Brain::random_neat(&mut rng, &mut tracker, &eyes)
+            .genome()
+            .unwrap()
+            .clone();
+
+        genome.mutate_add_node(&mut rng, &mut tracker);
+
+        let mut brain = Brain::from_genome(genome);
+        let vision = vec![0.5; eyes.cells()];
+
+        let mut scratch = PropagationBuffer::default();
+        let mut response = [0.0; 2];
+        brain.propagate_into(&vision, &mut scratch, &mut response);
+
+        let trace = brain.propagate_traced(vision);
+
+        assert_eq!(trace[1].len(), 1);
+        assert_eq!(trace[2].as_slice(), response.as_ref());
+        assert!(response.iter().all(|r| r.abs() <= 1.0));
+    }
+
+    #[test]
+    fn ctrnn_brain() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eyes = Eyes::default();
+        let brain = Brain::random(&mut rng, &mut tracker(&eyes), &eyes, BrainKind::Ctrnn);
+        let chromosome = brain.as_chromosome();
+
+        assert_eq!(chromosome.len(), 9 * 18 + 18 + 2 * (2 + 18 + 2));
+
+        let mut brain = Brain::from_chromosome(chromosome, &eyes, BrainKind::Ctrnn);
+        let mut scratch = PropagationBuffer::default();
+        let mut response = [0.0; 2];
+        let mut responses = Vec::new();
+
+        // Outputs can't jump straight to where the inputs push them
+        for _ in 0..3 {
+            brain.propagate_into(&[1.0; 9], &mut scratch, &mut response);
+            responses.push(response);
         }
+
+        assert_ne!(responses[0], responses[1]);
+        assert_ne!(responses[1], responses[2]);
+    }
+
+    #[test]
+    fn quantize() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eyes = Eyes::default();
+        let brain = Brain::random(&mut rng, &mut tracker(&eyes), &eyes, BrainKind::Feedforward);
+        let quantized = brain.quantize().unwrap();
+
+        let visions: Vec<_> = (0..10)
+            .map(|_| {
+                let foods: Vec<_> = (0..20).map(|_| Food::random(&mut rng)).collect();
+                let mut vision = vec![0.0; eyes.cells()];
+                eyes.process_vision(
+                    Point2::new(0.5, 0.5),
+                    Rotation2::new(0.0),
+                    &foods,
+                    &mut vision,
+                );
+                vision
+            })
+            .collect();
+
+        let BrainNetwork::Fixed(nn) = &brain.nn else {
+            unreachable!()
+        };
+
+        let report = quantized.error_report(nn, &visions);
+
+        assert!(report.max_output_error < 0.05, "{:?}", report);
+    }
+
+    #[test]
+    fn strategy_survives_roundtrip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let eyes = Eyes::default();
+        let chromosome =
+            Brain::random(&mut rng, &mut tracker(&eyes), &eyes, BrainKind::Feedforward)
+                .as_chromosome()
+                .with_strategy(vec![0.1; 10]);
+
+        let brain = Brain::from_chromosome(chromosome.clone(), &eyes, BrainKind::Feedforward);
+
+        assert_eq!(brain.as_chromosome(), chromosome);
+        assert_eq!(brain.as_chromosome().strategy(), &[0.1; 10]);
     }
 }
@@ -32,13 +32,18 @@ impl Eyes {
         self.cells
     }
 
+    /// Writes what the eye sees into `cells`, which must be exactly
+    /// `self.cells()` long.
     pub fn process_vision(
         &self,
         position: Point2<f32>,
         rotation: Rotation2<f32>,
         foods: &[Food],
-    ) -> Vec<f32> {
-        let mut cells = vec![0.0; self.cells];
+        cells: &mut [f32],
+    ) {
+        assert_eq!(cells.len(), self.cells);
+
+        cells.fill(0.0);
 
         for food in foods {
             let vec = food.position - position;
@@ -102,8 +107,6 @@ impl Eyes {
 
             cells[cell] += energy;
         }
-
-        cells
     }
 }
 
@@ -140,10 +143,14 @@ mod tests {
         fn run(self) {
             let eye = Eyes::new(self.fov_range, self.fov_angle, TEST_EYE_CELLS);
 
-            let actual_vision = eye.process_vision(
+            // Garbage from the previous step mustn't leak into the vision
+            let mut actual_vision = vec![1.0; TEST_EYE_CELLS];
+
+            eye.process_vision(
                 Point2::new(self.x, self.y),
                 Rotation2::new(self.rot),
                 &self.foods,
+                &mut actual_vision,
             );
             let actual_vision: Vec<_> = actual_vision
                 .into_iter()
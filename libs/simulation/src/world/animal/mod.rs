@@ -1,10 +1,14 @@
 use lib_genetic_algorithm::chromosome::Chromosome;
+use lib_neural_network::neat::{Genome, InnovationTracker};
 use nalgebra::{Point2, Rotation2};
 use rand::{distributions::Uniform, prelude::Distribution, Rng, RngCore};
 
 use crate::{SPEED_MAX, SPEED_MIN};
 
-use self::{brain::Brain, eyes::Eyes};
+use self::{
+    brain::{Brain, BrainKind},
+    eyes::Eyes,
+};
 
 pub mod brain;
 pub mod eyes;
@@ -30,10 +34,14 @@ impl Animal {
         }
     }
 
-    pub fn random(rng: &mut dyn RngCore) -> Animal {
+    pub fn random(
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+        brain: BrainKind,
+    ) -> Animal {
         let between = Uniform::from(SPEED_MIN..=SPEED_MAX);
         let eyes = Eyes::default();
-        let brain = Brain::random(rng, &eyes);
+        let brain = Brain::random(rng, tracker, &eyes, brain);
 
         Self {
             position: rng.gen(),
@@ -57,6 +65,14 @@ impl Animal {
         self.speed
     }
 
+    pub fn eyes(&self) -> &Eyes {
+        &self.eyes
+    }
+
+    pub fn brain(&self) -> &Brain {
+        &self.brain
+    }
+
     pub(crate) fn as_chromosome(&self) -> Chromosome {
         // We evolve only our birds' brains, but technically there's no
         // reason not to simulate e.g. physical properties such as size.
@@ -68,10 +84,18 @@ impl Animal {
         self.brain.as_chromosome()
     }
 
-    pub(crate) fn from_chromosome(chromosome: Chromosome, rng: &mut dyn RngCore) -> Self {
+    pub(crate) fn from_chromosome(
+        chromosome: Chromosome,
+        brain: BrainKind,
+        rng: &mut dyn RngCore,
+    ) -> Self {
         let eye = Eyes::default();
-        let brain = Brain::from_chromosome(chromosome, &eye);
+        let brain = Brain::from_chromosome(chromosome, &eye, brain);
 
         Self::new(eye, brain, rng)
     }
+
+    pub(crate) fn from_genome(genome: Genome, rng: &mut dyn RngCore) -> Self {
+        Self::new(Eyes::default(), Brain::from_genome(genome), rng)
+    }
 }
@@ -1,11 +1,14 @@
 use lib_genetic_algorithm::{chromosome::Chromosome, individual::Individual};
+use lib_neural_network::neat::Genome;
 use rand::RngCore;
 
-use super::Animal;
+use super::{animal::brain::BrainKind, Animal};
 
 pub struct AnimalIndividual {
     fitness: f32,
     chromosome: Chromosome,
+    // NEAT brains' topology, which the chromosome cannot carry
+    genome: Option<Genome>,
 }
 
 impl Individual for AnimalIndividual {
@@ -13,6 +16,7 @@ impl Individual for AnimalIndividual {
         Self {
             fitness: 0.0,
             chromosome,
+            genome: None,
         }
     }
 
@@ -30,10 +34,26 @@ impl AnimalIndividual {
         Self {
             fitness: animal.satiation as f32,
             chromosome: animal.as_chromosome(),
+            genome: animal.brain.genome().cloned(),
         }
     }
 
-    pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+    pub(crate) fn from_genome(genome: Genome) -> Self {
+        Self {
+            fitness: 0.0,
+            chromosome: genome.weights().collect(),
+            genome: Some(genome),
+        }
+    }
+
+    pub(crate) fn genome(&self) -> Option<&Genome> {
+        self.genome.as_ref()
+    }
+
+    pub fn into_animal(self, brain: BrainKind, rng: &mut dyn RngCore) -> Animal {
+        match self.genome {
+            Some(genome) => Animal::from_genome(genome, rng),
+            None => Animal::from_chromosome(self.chromosome, brain, rng),
+        }
     }
 }
@@ -1,6 +1,10 @@
+use lib_neural_network::neat::InnovationTracker;
 use rand::RngCore;
 
-pub use self::{animal::Animal, food::Food};
+pub use self::{
+    animal::{brain::BrainKind, Animal},
+    food::Food,
+};
 
 pub mod animal;
 pub mod food;
@@ -12,8 +16,14 @@ pub struct World {
 }
 
 impl World {
-    pub(crate) fn random(rng: &mut dyn RngCore) -> World {
-        let animals = (0..40).map(|_| Animal::random(rng)).collect();
+    pub(crate) fn random(
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+        brain: BrainKind,
+    ) -> World {
+        let animals = (0..40)
+            .map(|_| Animal::random(rng, tracker, brain))
+            .collect();
 
         let foods = (0..60).map(|_| Food::random(rng)).collect();
 